@@ -0,0 +1,68 @@
+use crate::days::{Day, DAYS};
+use aoc_common::{read_file, records, ParseError};
+use std::path::PathBuf;
+
+// Each day directory has an `answers.txt` manifest, one expected answer per line:
+//   <input file> <part> <answer>
+// Lines starting with `#` are comments.
+struct Expected {
+    file: String,
+    part: u8,
+    answer: String,
+}
+
+fn parse_manifest(contents: &str) -> Result<Vec<Expected>, ParseError> {
+    let mut expected = Vec::new();
+    for line in records(contents) {
+        if line.text.starts_with('#') {
+            continue;
+        }
+        let fields: Vec<&str> = line.text.split_whitespace().collect();
+        if fields.len() != 3 {
+            return Err(line.error(line.text, "expecting `<input file> <part> <answer>`"));
+        }
+        let part = line.parse::<u8>(fields[1])?;
+        if part != 1 && part != 2 {
+            return Err(line.error(fields[1], format!("invalid part `{}`, expecting 1 or 2", part)));
+        }
+        expected.push(Expected { file: fields[0].to_string(), part, answer: fields[2].to_string() });
+    }
+    Ok(expected)
+}
+
+fn day_dir(day: &Day) -> PathBuf {
+    day.default_input().parent().unwrap().to_path_buf()
+}
+
+// Return one message per wrong answer
+fn check(day: &Day) -> Vec<String> {
+    let manifest = day_dir(day).join("answers.txt").display().to_string();
+    let expected = match read_file(&manifest)
+        .and_then(|contents| parse_manifest(&contents).map_err(|e| e.in_file(&manifest)))
+    {
+        Ok(expected) => expected,
+        Err(e) => return vec![e.to_string()],
+    };
+    let mut failures = Vec::new();
+    for e in expected {
+        let f_name = day_dir(day).join(&e.file).display().to_string();
+        let answer = read_file(&f_name)
+            .map_err(|e| e.to_string())
+            .and_then(|contents| day.solve(&[e.part], &contents, &f_name));
+        match answer.map(|answers| answers[0].answer.clone()) {
+            Ok(answer) if answer == e.answer => (),
+            Ok(answer) => failures.push(format!(
+                "Day {} part {} on {}: expected {}, got {}",
+                day.number, e.part, e.file, e.answer, answer
+            )),
+            Err(msg) => failures.push(msg),
+        }
+    }
+    failures
+}
+
+#[test]
+fn every_day_gives_the_expected_answers() {
+    let failures: Vec<String> = DAYS.iter().flat_map(check).collect();
+    assert!(failures.is_empty(), "\n{}\n", failures.join("\n"));
+}
@@ -0,0 +1,138 @@
+use crate::days::Day;
+use aoc_common::{read_file, Answer, Format};
+use serde::Serialize;
+use std::fs;
+use std::panic;
+use std::path::Path;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::sync::Mutex;
+use std::thread;
+use std::time::Duration;
+
+/// The answers to both parts for one input file, or why it could not be solved
+pub struct FileResult {
+    pub file: String,
+    pub answers: Result<Vec<Answer>, String>,
+}
+
+#[derive(Serialize)]
+struct JsonAnswer<'a> {
+    file: &'a str,
+    #[serde(flatten)]
+    answer: &'a Answer,
+}
+
+#[derive(Serialize)]
+struct JsonError<'a> {
+    file: &'a str,
+    error: &'a str,
+}
+
+/// The files of `dir`, sorted by name
+pub fn list_inputs(dir: &str) -> Result<Vec<String>, String> {
+    let entries = fs::read_dir(dir).map_err(|e| format!("Error in reading directory {}: {}", dir, e))?;
+    let mut files = Vec::new();
+    for entry in entries {
+        let path = entry.map_err(|e| format!("Error in reading directory {}: {}", dir, e))?.path();
+        if path.is_file() {
+            files.push(path.display().to_string());
+        }
+    }
+    files.sort();
+    Ok(files)
+}
+
+/// Solve both parts for every file, spreading the files over `jobs` threads.
+/// The results are in the same order as the files.
+pub fn solve_all(day: &Day, files: &[String], jobs: usize) -> Vec<FileResult> {
+    let next = AtomicUsize::new(0);
+    let results: Mutex<Vec<Option<FileResult>>> = Mutex::new(files.iter().map(|_| None).collect());
+    thread::scope(|scope| {
+        for _ in 0..jobs.min(files.len()) {
+            scope.spawn(|| loop {
+                // each thread picks the next file not solved yet
+                let idx = next.fetch_add(1, Ordering::SeqCst);
+                let f_name = match files.get(idx) {
+                    Some(f_name) => f_name,
+                    None => break,
+                };
+                let answers = read_file(f_name)
+                    .map_err(|e| e.to_string())
+                    .and_then(|contents| solve_file(day, &contents, f_name));
+                results.lock().unwrap()[idx] = Some(FileResult { file: f_name.clone(), answers });
+            });
+        }
+    });
+    results.into_inner().unwrap().into_iter().map(|r| r.unwrap()).collect()
+}
+
+// Solve both parts, a solver panicking on the file being reported as an error on this file only.
+// A stack overflow is no panic though: it still aborts the whole run, so the parsers reject the
+// inputs the solvers would recurse on without end (like the day 7 bags containing themselves)
+fn solve_file(day: &Day, contents: &str, f_name: &str) -> Result<Vec<Answer>, String> {
+    panic::catch_unwind(|| day.solve(&[1, 2], contents, f_name)).unwrap_or_else(|payload| {
+        let msg = match (payload.downcast_ref::<&str>(), payload.downcast_ref::<String>()) {
+            (Some(msg), _) => msg.to_string(),
+            (_, Some(msg)) => msg.clone(),
+            _ => "unknown reason".to_string(),
+        };
+        Err(format!("{}: the solver panicked: {}", f_name, msg))
+    })
+}
+
+/// Print a table of the answers and timings, one row per file, or one JSON object per answer
+pub fn print(results: &[FileResult], format: Format) {
+    match format {
+        Format::Json => {
+            for result in results {
+                match &result.answers {
+                    Ok(answers) => {
+                        for answer in answers {
+                            let json = JsonAnswer { file: &result.file, answer };
+                            println!("{}", serde_json::to_string(&json).unwrap());
+                        }
+                    }
+                    Err(error) => {
+                        let json = JsonError { file: &result.file, error };
+                        println!("{}", serde_json::to_string(&json).unwrap());
+                    }
+                }
+            }
+        }
+        Format::Text => {
+            // errors don't count in the widths of the columns, except for the file
+            let header = ["FILE", "PART 1", "PART 2", "ELAPSED"].map(|s| s.to_string());
+            let mut widths = header.clone().map(|cell| cell.len());
+            for result in results {
+                widths[0] = widths[0].max(file_name(&result.file).len());
+                if let Ok(answers) = &result.answers {
+                    for (width, cell) in widths.iter_mut().zip(text_row(&result.file, answers).iter()) {
+                        *width = (*width).max(cell.len());
+                    }
+                }
+            }
+            print_row(&header, &widths);
+            for result in results {
+                match &result.answers {
+                    Ok(answers) => print_row(&text_row(&result.file, answers), &widths),
+                    // the error already mentions the file
+                    Err(error) => println!("{:<w$}  error: {}", file_name(&result.file), error, w = widths[0]),
+                }
+            }
+        }
+    }
+}
+
+fn file_name(f_name: &str) -> String {
+    Path::new(f_name).file_name().map_or(f_name.to_string(), |f| f.to_string_lossy().to_string())
+}
+
+fn text_row(f_name: &str, answers: &[Answer]) -> [String; 4] {
+    let elapsed: Duration = answers.iter().map(|a| a.elapsed()).sum();
+    [file_name(f_name), answers[0].to_string(), answers[1].to_string(), format!("{:?}", elapsed)]
+}
+
+fn print_row(row: &[String; 4], widths: &[usize; 4]) {
+    let cells: Vec<String> = row.iter().zip(widths.iter()).map(|(cell, w)| format!("{:<w$}", cell, w = w)).collect();
+    println!("{}", cells.join("  ").trim_end());
+}
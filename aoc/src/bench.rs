@@ -0,0 +1,177 @@
+use aoc_common::{ParseError, Solution};
+use serde::{Deserialize, Serialize};
+use std::hint::black_box;
+use std::time::{Duration, Instant};
+
+/// Durations of each step, one per iteration
+pub struct Samples {
+    parse: Vec<Duration>,
+    part1: Vec<Duration>,
+    part2: Vec<Duration>,
+}
+
+/// Time separately the parsing and both parts of a day, over `iterations` runs
+pub fn measure<S: Solution>(input: &str, iterations: usize) -> Result<Samples, ParseError> {
+    let mut samples = Samples { parse: Vec::new(), part1: Vec::new(), part2: Vec::new() };
+    for _ in 0..iterations {
+        let start = Instant::now();
+        let parsed = S::parse(black_box(input))?;
+        samples.parse.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part1(&parsed));
+        samples.part1.push(start.elapsed());
+
+        let start = Instant::now();
+        black_box(S::part2(&parsed));
+        samples.part2.push(start.elapsed());
+    }
+    Ok(samples)
+}
+
+/// Statistics over the durations of a step, in nanoseconds
+#[derive(Serialize, Deserialize)]
+pub struct Stats {
+    pub mean_ns: f64,
+    pub median_ns: f64,
+    pub stddev_ns: f64,
+}
+
+impl Stats {
+    fn from_samples(samples: &[Duration]) -> Stats {
+        let mut ns: Vec<f64> = samples.iter().map(|d| d.as_nanos() as f64).collect();
+        ns.sort_by(|a, b| a.partial_cmp(b).unwrap());
+        let n = ns.len() as f64;
+        let mean = ns.iter().sum::<f64>() / n;
+        let median = if ns.len() % 2 == 1 {
+            ns[ns.len() / 2]
+        } else {
+            (ns[ns.len() / 2 - 1] + ns[ns.len() / 2]) / 2.0
+        };
+        // sample standard deviation, 0 when there is a single iteration
+        let variance = if ns.len() > 1 {
+            ns.iter().map(|x| (x - mean) * (x - mean)).sum::<f64>() / (n - 1.0)
+        } else {
+            0.0
+        };
+        Stats { mean_ns: mean, median_ns: median, stddev_ns: variance.sqrt() }
+    }
+}
+
+/// The statistics of each step of a day
+#[derive(Serialize, Deserialize)]
+pub struct DayReport {
+    pub day: u8,
+    /// Nb of records of the generated input, when not run on the puzzle input
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub size: Option<usize>,
+    pub parse: Stats,
+    pub part1: Stats,
+    pub part2: Stats,
+}
+
+impl DayReport {
+    pub fn new(day: u8, size: Option<usize>, samples: &Samples) -> DayReport {
+        DayReport {
+            day,
+            size,
+            parse: Stats::from_samples(&samples.parse),
+            part1: Stats::from_samples(&samples.part1),
+            part2: Stats::from_samples(&samples.part2),
+        }
+    }
+
+    fn steps(&self) -> [(&'static str, &Stats); 3] {
+        [("parse", &self.parse), ("part 1", &self.part1), ("part 2", &self.part2)]
+    }
+
+    // "Day  1", followed by the size of the input when generated
+    fn name(&self) -> String {
+        match self.size {
+            Some(size) => format!("Day {:>2} size {:>8}", self.day, size),
+            None => format!("Day {:>2}", self.day),
+        }
+    }
+}
+
+/// A whole benchmark run, as written to the JSON report
+#[derive(Serialize, Deserialize)]
+pub struct Report {
+    pub iterations: usize,
+    pub days: Vec<DayReport>,
+}
+
+impl Report {
+    pub fn to_json(&self) -> String {
+        serde_json::to_string_pretty(self).unwrap()
+    }
+
+    pub fn from_json(json: &str) -> Result<Report, String> {
+        serde_json::from_str(json).map_err(|e| format!("Invalid benchmark report: {}", e))
+    }
+}
+
+fn ns(ns: f64) -> Duration {
+    Duration::from_nanos(ns as u64)
+}
+
+/// One line per step: mean, median and standard deviation
+pub fn print_day(report: &DayReport) {
+    for (step, stats) in report.steps().iter() {
+        println!(
+            "{} {:<6}  mean {:>12?}  median {:>12?}  stddev {:>12?}",
+            report.name(),
+            step,
+            ns(stats.mean_ns),
+            ns(stats.median_ns),
+            ns(stats.stddev_ns)
+        );
+    }
+}
+
+/// Compare the medians of the steps found in both reports.
+/// Return the nb of steps that got slower by more than `threshold` percent.
+pub fn diff(old: &Report, new: &Report, threshold: f64) -> usize {
+    let mut regressions = 0;
+    for new_day in &new.days {
+        let old_day = match old.days.iter().find(|d| d.day == new_day.day && d.size == new_day.size) {
+            Some(old_day) => old_day,
+            None => {
+                println!("{}: not in the old report", new_day.name());
+                continue;
+            }
+        };
+        for ((step, old_stats), (_, new_stats)) in old_day.steps().iter().zip(new_day.steps().iter()) {
+            let change =
+                (new_stats.median_ns - old_stats.median_ns) / old_stats.median_ns.max(1.0) * 100.0;
+            let regression = change > threshold;
+            if regression {
+                regressions += 1;
+            }
+            println!(
+                "{} {:<6}  {:>12?} -> {:>12?}  {:>+7.1}%{}",
+                new_day.name(),
+                step,
+                ns(old_stats.median_ns),
+                ns(new_stats.median_ns),
+                change,
+                if regression { "  REGRESSION" } else { "" }
+            );
+        }
+    }
+    regressions
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn stats() {
+        let samples: Vec<Duration> = [2, 4, 4, 4, 5, 5, 7, 9].iter().map(|&n| Duration::from_nanos(n)).collect();
+        let stats = Stats::from_samples(&samples);
+        assert_eq!(5.0, stats.mean_ns);
+        assert_eq!(4.5, stats.median_ns);
+        assert!((stats.stddev_ns - (32.0f64 / 7.0).sqrt()).abs() < 1e-9);
+    }
+}
@@ -0,0 +1,145 @@
+use crate::bench::{self, Samples};
+use crate::profile::{self, DayUsage};
+use aoc_common::{Answer, Generator, ParseError, Recorder, Rng, Solution, Traced};
+use std::ops::RangeInclusive;
+use std::path::PathBuf;
+
+// day number, parts to solve, puzzle input
+type SolveFn = fn(u8, &[u8], &str) -> Result<Vec<Answer>, ParseError>;
+// part to trace, puzzle input, where to record the steps
+type TraceFn = fn(u8, &str, &mut Recorder) -> Result<(), ParseError>;
+
+pub struct Day {
+    pub number: u8,
+    input: &'static str, // default puzzle input, in the day directory
+    solve: SolveFn,
+    bench: fn(&str, usize) -> Result<Samples, ParseError>,
+    profile: fn(u8, &str) -> Result<DayUsage, ParseError>,
+    check: fn(&str) -> Vec<ParseError>,
+    generate: fn(usize, &mut Rng) -> String,
+    sizes: RangeInclusive<usize>, // of the generated inputs
+    trace: Option<TraceFn>, // for the days simulating something step by step
+}
+
+impl Day {
+    pub fn default_input(&self) -> PathBuf {
+        PathBuf::from(env!("CARGO_MANIFEST_DIR"))
+            .join("..")
+            .join(format!("day-{:02}", self.number))
+            .join(self.input)
+    }
+
+    // Parse the input once, then solve each of the `parts`.
+    // `f_name` is only used to locate the parse errors
+    pub fn solve(&self, parts: &[u8], input: &str, f_name: &str) -> Result<Vec<Answer>, String> {
+        if let Some(part) = parts.iter().find(|&&part| part != 1 && part != 2) {
+            return Err(format!("There is no part {}, expecting 1 or 2", part));
+        }
+        (self.solve)(self.number, parts, input).map_err(|e| e.in_file(f_name).to_string())
+    }
+
+    pub fn bench(&self, input: &str, f_name: &str, iterations: usize) -> Result<Samples, String> {
+        (self.bench)(input, iterations).map_err(|e| e.in_file(f_name).to_string())
+    }
+
+    // The memory allocated while parsing the input and solving each part
+    pub fn profile(&self, input: &str, f_name: &str) -> Result<DayUsage, String> {
+        (self.profile)(self.number, input).map_err(|e| e.in_file(f_name).to_string())
+    }
+
+    // Every error found in the input, without solving anything
+    pub fn check(&self, input: &str, f_name: &str) -> Vec<ParseError> {
+        (self.check)(input).into_iter().map(|e| e.in_file(f_name)).collect()
+    }
+
+    // The sizes of the generated inputs
+    pub fn sizes(&self) -> RangeInclusive<usize> {
+        self.sizes.clone()
+    }
+
+    // A made up puzzle input of about `size` records, always the same for a given seed
+    pub fn generate(&self, size: usize, seed: u64) -> Result<String, String> {
+        if size < *self.sizes.start() {
+            return Err(format!("Day {} makes up inputs of size {} at least, not {}", self.number, self.sizes.start(), size));
+        }
+        if size > *self.sizes.end() {
+            return Err(format!("Day {} makes up inputs of size {} at most, not {}", self.number, self.sizes.end(), size));
+        }
+        Ok((self.generate)(size, &mut Rng::new(seed)))
+    }
+
+    // Solve `part` again, recording each step of the simulation
+    pub fn trace(&self, part: u8, input: &str, f_name: &str, recorder: &mut Recorder) -> Result<(), String> {
+        let trace = self.trace.ok_or(format!("Day {} can't be traced", self.number))?;
+        trace(part, input, recorder).map_err(|e| e.in_file(f_name).to_string())
+    }
+}
+
+fn solve<S: Solution>(day: u8, parts: &[u8], input: &str) -> Result<Vec<Answer>, ParseError> {
+    let parsed = S::parse(input)?;
+    let answers = parts
+        .iter()
+        .map(|&part| {
+            if part == 1 {
+                Answer::solve(day, part, || S::part1(&parsed))
+            } else {
+                Answer::solve(day, part, || S::part2(&parsed))
+            }
+        })
+        .collect();
+    Ok(answers)
+}
+
+fn trace<S: Traced>(part: u8, input: &str, recorder: &mut Recorder) -> Result<(), ParseError> {
+    let parsed = S::parse(input)?;
+    S::trace(&parsed, part, recorder);
+    Ok(())
+}
+
+// every entry point of a day, generic over its `Solution`
+macro_rules! day {
+    ($number:expr, $input:expr, $solution:ty) => {
+        Day {
+            number: $number,
+            input: $input,
+            solve: solve::<$solution>,
+            bench: bench::measure::<$solution>,
+            profile: profile::measure_day::<$solution>,
+            check: <$solution>::check,
+            generate: <$solution>::generate,
+            sizes: <$solution as Generator>::MIN_SIZE..=<$solution as Generator>::MAX_SIZE,
+            trace: None,
+        }
+    };
+    ($number:expr, $input:expr, $solution:ty, traced) => {
+        Day { trace: Some(trace::<$solution>), ..day!($number, $input, $solution) }
+    };
+}
+
+pub const DAYS: &[Day] = &[
+    day!(1, "input", day_1::Day1),
+    day!(2, "input.txt", day_2::Day2),
+    day!(3, "input.txt", day_3::Day3, traced),
+    day!(4, "input.txt", day_4::Day4),
+    day!(5, "input.txt", day_5::Day5),
+    day!(6, "input.txt", day_6::Day6),
+    day!(7, "input.txt", day_7::Day7),
+    day!(8, "input.txt", day_8::Day8, traced),
+    day!(9, "input.txt", day_9::Day9),
+    day!(10, "input.txt", day_10::Day10),
+    day!(11, "input.txt", day_11::Day11, traced),
+    day!(12, "input.txt", day_12::Day12, traced),
+    day!(13, "input.txt", day_13::Day13),
+    day!(14, "input.txt", day_14::Day14),
+    day!(15, "input.txt", day_15::Day15),
+    day!(16, "input.txt", day_16::Day16),
+    day!(18, "input.txt", day_18::Day18),
+    day!(21, "input.txt", day_21::Day21),
+    day!(22, "input.txt", day_22::Day22, traced),
+];
+
+pub fn find(number: u8) -> Result<&'static Day, String> {
+    DAYS.iter()
+        .find(|d| d.number == number)
+        .ok_or(format!("Day {} is not solved (yet?)", number))
+}
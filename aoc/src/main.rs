@@ -0,0 +1,630 @@
+use aoc_common::{Format, Recorder};
+use std::env;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::process;
+use std::thread;
+
+#[cfg(test)]
+mod answers;
+mod batch;
+mod bench;
+mod days;
+mod profile;
+#[cfg(test)]
+mod robustness;
+mod store;
+mod trace;
+mod tui;
+
+use profile::Counting;
+use store::{Outcome, Store};
+use trace::TraceFormat;
+
+// counts nothing but for `aoc profile`
+#[global_allocator]
+static ALLOCATOR: Counting = Counting;
+
+const USAGE: &str = "Usage: aoc run <day> [--part 1|2] [--input PATH|-] [--format text|json] [--no-store]
+       aoc batch <day> <DIR> [--jobs N] [--format text|json]
+       aoc check <day> [PATH|-]...
+       aoc gen <day> [--size N] [--seed SEED]
+       aoc trace <day> [--part 1|2] [--input PATH|-] [--format text|json|ppm|gif] [--output PATH] [--limit N]
+       aoc answers list|export [--all]
+       aoc answers verify|forget <day> [--part 1|2] [--input PATH|-]
+       aoc tui
+       aoc bench [<day>] [--iterations N] [--size N]... [--output REPORT.json]
+       aoc profile [<day>] [--output REPORT.json]
+       aoc bench-diff <OLD.json> <NEW.json> [--threshold PERCENT]";
+
+const DEFAULT_ITERATIONS: usize = 10;
+const DEFAULT_THRESHOLD: f64 = 10.0; // percent
+const DEFAULT_SIZE: usize = 100;
+
+struct RunArgs {
+    day: u8,
+    part: Option<u8>, // both parts when not given
+    input: Option<String>,
+    format: Format,
+    store: bool, // compare the answers with the stored ones
+}
+
+struct BatchArgs {
+    day: u8,
+    dir: String,
+    jobs: usize,
+    format: Format,
+}
+
+struct CheckArgs {
+    day: u8,
+    inputs: Vec<String>, // the default input when empty
+}
+
+struct GenArgs {
+    day: u8,
+    size: Option<usize>, // DEFAULT_SIZE when not given, or the nearest size the day makes up
+    seed: u64,
+}
+
+struct TraceArgs {
+    day: u8,
+    part: u8,
+    input: Option<String>,
+    format: TraceFormat,
+    output: Option<String>, // a file, or a directory for the PPM images
+    limit: Option<usize>,   // max nb of steps recorded
+}
+
+enum AnswersArgs {
+    List,
+    Export { all: bool }, // only the verified answers unless `all`
+    Verify { day: u8, part: Option<u8>, input: Option<String> },
+    Forget { day: u8, part: Option<u8>, input: Option<String> },
+}
+
+struct BenchArgs {
+    day: Option<u8>, // all the days when not given
+    iterations: usize,
+    sizes: Vec<usize>, // generated inputs of these sizes, rather than the puzzle inputs
+    output: Option<String>,
+}
+
+struct ProfileArgs {
+    day: Option<u8>, // all the days when not given
+    output: Option<String>,
+}
+
+struct DiffArgs {
+    old: String,
+    new: String,
+    threshold: f64,
+}
+
+fn main() {
+    let args: Vec<String> = env::args().skip(1).collect();
+    let result = match args.first().map(|s| s.as_str()) {
+        Some("run") => parse_run_args(&args[1..]).and_then(|run_args| run(&run_args)),
+        Some("batch") => parse_batch_args(&args[1..]).and_then(|batch_args| batch(&batch_args)),
+        Some("check") => parse_check_args(&args[1..]).and_then(|check_args| check(&check_args)),
+        Some("gen") => parse_gen_args(&args[1..]).and_then(|gen_args| gen(&gen_args)),
+        Some("trace") => parse_trace_args(&args[1..]).and_then(|trace_args| trace(&trace_args)),
+        Some("answers") => parse_answers_args(&args[1..]).and_then(|answers_args| answers(&answers_args)),
+        Some("tui") if args.len() == 1 => tui::run(),
+        Some("bench") => parse_bench_args(&args[1..]).and_then(|bench_args| bench(&bench_args)),
+        Some("profile") => parse_profile_args(&args[1..]).and_then(|profile_args| profile(&profile_args)),
+        Some("bench-diff") => parse_diff_args(&args[1..]).and_then(|diff_args| bench_diff(&diff_args)),
+        Some("-h") | Some("--help") => {
+            println!("{}", USAGE);
+            Ok(())
+        }
+        _ => Err(USAGE.to_string()),
+    };
+    if let Err(e) = result {
+        eprintln!("{}", e);
+        process::exit(1);
+    }
+}
+
+fn parse_run_args(args: &[String]) -> Result<RunArgs, String> {
+    let mut iter = args.iter();
+    let day = match iter.next() {
+        Some(day) => day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?,
+        None => return Err(USAGE.to_string()),
+    };
+    let mut run_args = RunArgs { day, part: None, input: None, format: Format::Text, store: true };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = iter.next().ok_or(format!("Missing value for --part\n{}", USAGE))?;
+                match part.as_str() {
+                    "1" => run_args.part = Some(1),
+                    "2" => run_args.part = Some(2),
+                    _ => return Err(format!("Invalid part `{}`, expecting 1 or 2", part)),
+                }
+            }
+            "--input" => {
+                let input = iter.next().ok_or(format!("Missing value for --input\n{}", USAGE))?;
+                run_args.input = Some(input.clone());
+            }
+            "--format" => {
+                let format = iter.next().ok_or(format!("Missing value for --format\n{}", USAGE))?;
+                run_args.format = format.parse()?;
+            }
+            "--no-store" => run_args.store = false,
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(run_args)
+}
+
+fn run(run_args: &RunArgs) -> Result<(), String> {
+    let day = days::find(run_args.day)?;
+    let f_in = match &run_args.input {
+        Some(path) => path.into(),
+        None => day.default_input(),
+    };
+    let f_name = f_in.display().to_string();
+    let contents = aoc_common::read_file(&f_name).map_err(|e| e.to_string())?;
+
+    let parts = match run_args.part {
+        Some(part) => vec![part],
+        None => vec![1, 2],
+    };
+    let answers = day.solve(&parts, &contents, &f_name)?;
+    for answer in &answers {
+        match run_args.format {
+            Format::Text => println!("Day {} part {} = {} (after {:?})", day.number, answer.part, answer, answer.elapsed()),
+            Format::Json => println!("{}", answer.to_json()),
+        }
+    }
+    if !run_args.store {
+        return Ok(());
+    }
+
+    // compare with the answers given the previous times, on the same input.
+    // A `none` is stored without its reason, which may be reworded
+    let mut store = Store::load(Store::default_path())?;
+    let input = store::hash(&contents);
+    let mut changed = 0;
+    let mut new = 0;
+    for answer in &answers {
+        match store.record(day.number, answer.part, &input, &f_name, &answer.answer) {
+            Outcome::New => new += 1,
+            Outcome::Same { .. } => (),
+            Outcome::Changed { stored } => {
+                changed += 1;
+                let verified = if stored.verified { ", verified as correct" } else { "" };
+                eprintln!("!!! Day {} part {} = {}, but the answer stored for this input is {}{} !!!", day.number, answer.part, answer, stored.answer, verified);
+            }
+        }
+    }
+    if new > 0 {
+        store.save()?;
+    }
+    if changed > 0 {
+        return Err(format!("{} answer(s) changed: run `aoc answers forget {}` to store the new ones", changed, day.number));
+    }
+    Ok(())
+}
+
+fn parse_batch_args(args: &[String]) -> Result<BatchArgs, String> {
+    let mut iter = args.iter();
+    let day = match iter.next() {
+        Some(day) => day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?,
+        None => return Err(USAGE.to_string()),
+    };
+    let dir = iter.next().ok_or(format!("Missing input directory\n{}", USAGE))?.clone();
+    let jobs = thread::available_parallelism().map_or(1, |n| n.get());
+    let mut batch_args = BatchArgs { day, dir, jobs, format: Format::Text };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--jobs" => {
+                let jobs = iter.next().ok_or(format!("Missing value for --jobs\n{}", USAGE))?;
+                batch_args.jobs = match jobs.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid nb of jobs `{}`", jobs)),
+                };
+            }
+            "--format" => {
+                let format = iter.next().ok_or(format!("Missing value for --format\n{}", USAGE))?;
+                batch_args.format = format.parse()?;
+            }
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(batch_args)
+}
+
+fn batch(batch_args: &BatchArgs) -> Result<(), String> {
+    let day = days::find(batch_args.day)?;
+    let files = batch::list_inputs(&batch_args.dir)?;
+    let results = batch::solve_all(day, &files, batch_args.jobs);
+    batch::print(&results, batch_args.format);
+    let failed = results.iter().filter(|r| r.answers.is_err()).count();
+    if failed > 0 {
+        return Err(format!("{} file(s) out of {} could not be solved", failed, files.len()));
+    }
+    Ok(())
+}
+
+fn parse_check_args(args: &[String]) -> Result<CheckArgs, String> {
+    let mut iter = args.iter();
+    let day = match iter.next() {
+        Some(day) => day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?,
+        None => return Err(USAGE.to_string()),
+    };
+    let mut check_args = CheckArgs { day, inputs: Vec::new() };
+    for arg in iter {
+        match arg.as_str() {
+            input if input == aoc_common::STDIN || !input.starts_with('-') => check_args.inputs.push(input.to_string()),
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(check_args)
+}
+
+// Report every error of every input file, like a linter
+fn check(check_args: &CheckArgs) -> Result<(), String> {
+    let day = days::find(check_args.day)?;
+    let f_names = if check_args.inputs.is_empty() {
+        vec![day.default_input().display().to_string()]
+    } else {
+        check_args.inputs.clone()
+    };
+    let mut failed = 0;
+    for f_name in &f_names {
+        let errors = match aoc_common::read_file(f_name) {
+            Ok(contents) => day.check(&contents, f_name),
+            Err(e) => vec![e],
+        };
+        if errors.is_empty() {
+            println!("{}: ok", f_name);
+        } else {
+            failed += 1;
+        }
+        for e in errors {
+            println!("{}", e);
+        }
+    }
+    if failed > 0 {
+        return Err(format!("{} file(s) out of {} are malformed", failed, f_names.len()));
+    }
+    Ok(())
+}
+
+fn parse_gen_args(args: &[String]) -> Result<GenArgs, String> {
+    let mut iter = args.iter();
+    let day = match iter.next() {
+        Some(day) => day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?,
+        None => return Err(USAGE.to_string()),
+    };
+    let mut gen_args = GenArgs { day, size: None, seed: 0 };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--size" => {
+                let size = iter.next().ok_or(format!("Missing value for --size\n{}", USAGE))?;
+                gen_args.size = Some(size.parse::<usize>().map_err(|_| format!("Invalid size `{}`", size))?);
+            }
+            "--seed" => {
+                let seed = iter.next().ok_or(format!("Missing value for --seed\n{}", USAGE))?;
+                gen_args.seed = seed.parse::<u64>().map_err(|_| format!("Invalid seed `{}`", seed))?;
+            }
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(gen_args)
+}
+
+fn gen(gen_args: &GenArgs) -> Result<(), String> {
+    let day = days::find(gen_args.day)?;
+    let size = gen_args.size.unwrap_or_else(|| DEFAULT_SIZE.clamp(*day.sizes().start(), *day.sizes().end()));
+    print!("{}", day.generate(size, gen_args.seed)?);
+    Ok(())
+}
+
+fn parse_trace_args(args: &[String]) -> Result<TraceArgs, String> {
+    let mut iter = args.iter();
+    let day = match iter.next() {
+        Some(day) => day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?,
+        None => return Err(USAGE.to_string()),
+    };
+    let mut trace_args = TraceArgs { day, part: 1, input: None, format: TraceFormat::Text, output: None, limit: None };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--part" => {
+                let part = iter.next().ok_or(format!("Missing value for --part\n{}", USAGE))?;
+                match part.as_str() {
+                    "1" => trace_args.part = 1,
+                    "2" => trace_args.part = 2,
+                    _ => return Err(format!("Invalid part `{}`, expecting 1 or 2", part)),
+                }
+            }
+            "--input" => {
+                let input = iter.next().ok_or(format!("Missing value for --input\n{}", USAGE))?;
+                trace_args.input = Some(input.clone());
+            }
+            "--format" => {
+                let format = iter.next().ok_or(format!("Missing value for --format\n{}", USAGE))?;
+                trace_args.format = format.parse()?;
+            }
+            "--output" => {
+                let output = iter.next().ok_or(format!("Missing value for --output\n{}", USAGE))?;
+                trace_args.output = Some(output.clone());
+            }
+            "--limit" => {
+                let limit = iter.next().ok_or(format!("Missing value for --limit\n{}", USAGE))?;
+                trace_args.limit = Some(limit.parse::<usize>().map_err(|_| format!("Invalid limit `{}`", limit))?);
+            }
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(trace_args)
+}
+
+// Record each step of the simulation of a day, and export them
+fn trace(trace_args: &TraceArgs) -> Result<(), String> {
+    let day = days::find(trace_args.day)?;
+    let f_in = match &trace_args.input {
+        Some(path) => path.into(),
+        None => day.default_input(),
+    };
+    let f_name = f_in.display().to_string();
+    let contents = aoc_common::read_file(&f_name).map_err(|e| e.to_string())?;
+    let mut recorder = Recorder::new(trace_args.limit);
+    day.trace(trace_args.part, &contents, &f_name, &mut recorder)?;
+
+    let frames = &recorder.frames;
+    match (trace_args.format, &trace_args.output) {
+        (TraceFormat::Ppm, Some(dir)) => trace::write_ppm(frames, Path::new(dir))?,
+        (TraceFormat::Gif, Some(output)) => trace::write_gif(frames, Path::new(output))?,
+        (TraceFormat::Ppm, None) | (TraceFormat::Gif, None) => return Err(format!("Missing --output, to write the images to\n{}", USAGE)),
+        (format, output) => {
+            let write = |w: &mut dyn Write| -> io::Result<()> {
+                let mut w = BufWriter::new(w);
+                if format == TraceFormat::Text { trace::write_text(frames, &mut w)? } else { trace::write_json(frames, &mut w)? }
+                w.flush()
+            };
+            match output {
+                Some(output) => File::create(output).and_then(|mut f| write(&mut f)).map_err(|e| format!("Error in writing file {}: {}", output, e))?,
+                None => write(&mut io::stdout().lock()).map_err(|e| e.to_string())?,
+            }
+        }
+    }
+    if recorder.dropped > 0 {
+        eprintln!("{} step(s) not recorded, past the limit of {}", recorder.dropped, recorder.frames.len());
+    }
+    Ok(())
+}
+
+fn parse_answers_args(args: &[String]) -> Result<AnswersArgs, String> {
+    let mut iter = args.iter();
+    let command = iter.next().ok_or_else(|| USAGE.to_string())?;
+    match command.as_str() {
+        "list" => match iter.next() {
+            None => Ok(AnswersArgs::List),
+            Some(arg) => Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        },
+        "export" => match iter.next().map(|arg| arg.as_str()) {
+            None => Ok(AnswersArgs::Export { all: false }),
+            Some("--all") => Ok(AnswersArgs::Export { all: true }),
+            Some(arg) => Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        },
+        "verify" | "forget" => {
+            let day = match iter.next() {
+                Some(day) => day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?,
+                None => return Err(USAGE.to_string()),
+            };
+            let mut part = None;
+            let mut input = None;
+            while let Some(arg) = iter.next() {
+                match arg.as_str() {
+                    "--part" => {
+                        let value = iter.next().ok_or(format!("Missing value for --part\n{}", USAGE))?;
+                        match value.as_str() {
+                            "1" => part = Some(1),
+                            "2" => part = Some(2),
+                            _ => return Err(format!("Invalid part `{}`, expecting 1 or 2", value)),
+                        }
+                    }
+                    "--input" => {
+                        let value = iter.next().ok_or(format!("Missing value for --input\n{}", USAGE))?;
+                        input = Some(value.clone());
+                    }
+                    _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+                }
+            }
+            if command == "verify" {
+                Ok(AnswersArgs::Verify { day, part, input })
+            } else {
+                Ok(AnswersArgs::Forget { day, part, input })
+            }
+        }
+        _ => Err(format!("Unknown command `answers {}`\n{}", command, USAGE)),
+    }
+}
+
+// Look at, or amend, the answers stored by `aoc run`
+fn answers(answers_args: &AnswersArgs) -> Result<(), String> {
+    let mut store = Store::load(Store::default_path())?;
+    // the hash of the input of a day, and its file name
+    let input_of = |day: u8, input: &Option<String>| -> Result<(String, String), String> {
+        let day = days::find(day)?;
+        let f_name = match input {
+            Some(path) => path.clone(),
+            None => day.default_input().display().to_string(),
+        };
+        let contents = aoc_common::read_file(&f_name).map_err(|e| e.to_string())?;
+        Ok((store::hash(&contents), f_name))
+    };
+    match answers_args {
+        AnswersArgs::List => {
+            for e in store.entries() {
+                let verified = if e.verified { " (verified)" } else { "" };
+                println!("Day {} part {} = {}{} on {} [{}]", e.day, e.part, e.answer, verified, e.file, e.input);
+            }
+        }
+        AnswersArgs::Export { all } => {
+            for e in store.entries().iter().filter(|e| *all || e.verified) {
+                println!("{}", serde_json::to_string(e).unwrap());
+            }
+        }
+        AnswersArgs::Verify { day, part, input } => {
+            let (hash, f_name) = input_of(*day, input)?;
+            match store.verify(*day, *part, &hash) {
+                0 => return Err(format!("No answer of day {} stored for {}: run it first", day, f_name)),
+                marked => println!("{} answer(s) of day {} on {} verified", marked, day, f_name),
+            }
+            store.save()?;
+        }
+        AnswersArgs::Forget { day, part, input } => {
+            let (hash, f_name) = input_of(*day, input)?;
+            let removed = store.forget(*day, *part, &hash);
+            println!("{} answer(s) of day {} on {} forgotten", removed, day, f_name);
+            store.save()?;
+        }
+    }
+    Ok(())
+}
+
+fn parse_bench_args(args: &[String]) -> Result<BenchArgs, String> {
+    let mut iter = args.iter();
+    let mut bench_args = BenchArgs { day: None, iterations: DEFAULT_ITERATIONS, sizes: Vec::new(), output: None };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--iterations" => {
+                let iterations = iter.next().ok_or(format!("Missing value for --iterations\n{}", USAGE))?;
+                bench_args.iterations = match iterations.parse::<usize>() {
+                    Ok(n) if n > 0 => n,
+                    _ => return Err(format!("Invalid nb of iterations `{}`", iterations)),
+                };
+            }
+            "--size" => {
+                let size = iter.next().ok_or(format!("Missing value for --size\n{}", USAGE))?;
+                bench_args.sizes.push(size.parse::<usize>().map_err(|_| format!("Invalid size `{}`", size))?);
+            }
+            "--output" => {
+                let output = iter.next().ok_or(format!("Missing value for --output\n{}", USAGE))?;
+                bench_args.output = Some(output.clone());
+            }
+            day if bench_args.day.is_none() && !day.starts_with("--") => {
+                bench_args.day = Some(day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?);
+            }
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(bench_args)
+}
+
+fn bench(bench_args: &BenchArgs) -> Result<(), String> {
+    let days = match bench_args.day {
+        Some(number) => vec![days::find(number)?],
+        None => days::DAYS.iter().collect(),
+    };
+    let mut report = bench::Report { iterations: bench_args.iterations, days: Vec::new() };
+    for day in days {
+        if bench_args.sizes.is_empty() {
+            let f_name = day.default_input().display().to_string();
+            let contents = aoc_common::read_file(&f_name).map_err(|e| e.to_string())?;
+            let samples = day.bench(&contents, &f_name, bench_args.iterations)?;
+            let day_report = bench::DayReport::new(day.number, None, &samples);
+            bench::print_day(&day_report);
+            report.days.push(day_report);
+        }
+        // how the time grows with the size of the input
+        for &size in &bench_args.sizes {
+            // the other days are still benched at this size
+            let contents = match day.generate(size, 0) {
+                Ok(contents) => contents,
+                Err(e) => {
+                    eprintln!("{}: skipped", e);
+                    continue;
+                }
+            };
+            let samples = day.bench(&contents, &format!("generated input of size {}", size), bench_args.iterations)?;
+            let day_report = bench::DayReport::new(day.number, Some(size), &samples);
+            bench::print_day(&day_report);
+            report.days.push(day_report);
+        }
+    }
+    if let Some(output) = &bench_args.output {
+        fs::write(output, report.to_json()).map_err(|e| format!("Error in writing file {}: {}", output, e))?;
+    }
+    Ok(())
+}
+
+fn parse_profile_args(args: &[String]) -> Result<ProfileArgs, String> {
+    let mut iter = args.iter();
+    let mut profile_args = ProfileArgs { day: None, output: None };
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--output" => {
+                let output = iter.next().ok_or(format!("Missing value for --output\n{}", USAGE))?;
+                profile_args.output = Some(output.clone());
+            }
+            day if profile_args.day.is_none() && !day.starts_with("--") => {
+                profile_args.day = Some(day.parse::<u8>().map_err(|_| format!("Invalid day `{}`\n{}", day, USAGE))?);
+            }
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    Ok(profile_args)
+}
+
+// Count the allocations of each step of the days
+fn profile(profile_args: &ProfileArgs) -> Result<(), String> {
+    let days = match profile_args.day {
+        Some(number) => vec![days::find(number)?],
+        None => days::DAYS.iter().collect(),
+    };
+    let mut report = Vec::new();
+    for day in days {
+        let f_name = day.default_input().display().to_string();
+        let contents = aoc_common::read_file(&f_name).map_err(|e| e.to_string())?;
+        let usage = day.profile(&contents, &f_name)?;
+        profile::print_day(&usage);
+        report.push(usage);
+    }
+    if let Some(output) = &profile_args.output {
+        let json = serde_json::to_string_pretty(&report).unwrap();
+        fs::write(output, json).map_err(|e| format!("Error in writing file {}: {}", output, e))?;
+    }
+    Ok(())
+}
+
+fn parse_diff_args(args: &[String]) -> Result<DiffArgs, String> {
+    let mut iter = args.iter();
+    let mut reports = Vec::new();
+    let mut threshold = DEFAULT_THRESHOLD;
+    while let Some(arg) = iter.next() {
+        match arg.as_str() {
+            "--threshold" => {
+                let value = iter.next().ok_or(format!("Missing value for --threshold\n{}", USAGE))?;
+                threshold = value.parse::<f64>().map_err(|_| format!("Invalid threshold `{}`", value))?;
+            }
+            report if !report.starts_with("--") => reports.push(report.to_string()),
+            _ => return Err(format!("Unexpected argument `{}`\n{}", arg, USAGE)),
+        }
+    }
+    if reports.len() != 2 {
+        return Err(USAGE.to_string());
+    }
+    let new = reports.pop().unwrap();
+    let old = reports.pop().unwrap();
+    Ok(DiffArgs { old, new, threshold })
+}
+
+fn bench_diff(diff_args: &DiffArgs) -> Result<(), String> {
+    let read_report = |f_name: &str| {
+        let json = aoc_common::read_file(f_name).map_err(|e| e.to_string())?;
+        bench::Report::from_json(&json).map_err(|e| format!("{}: {}", f_name, e))
+    };
+    let old = read_report(&diff_args.old)?;
+    let new = read_report(&diff_args.new)?;
+    let regressions = bench::diff(&old, &new, diff_args.threshold);
+    if regressions > 0 {
+        return Err(format!("{} step(s) slower by more than {}%", regressions, diff_args.threshold));
+    }
+    Ok(())
+}
@@ -0,0 +1,159 @@
+use aoc_common::{ParseError, Solution};
+use serde::Serialize;
+use std::alloc::{GlobalAlloc, Layout, System};
+use std::hint::black_box;
+use std::sync::atomic::{AtomicBool, AtomicUsize, Ordering};
+
+// Nothing is counted until a measure starts: the other commands only pay for a flag check
+static COUNTING: AtomicBool = AtomicBool::new(false);
+static ALLOCATIONS: AtomicUsize = AtomicUsize::new(0);
+static BYTES: AtomicUsize = AtomicUsize::new(0);
+static CURRENT: AtomicUsize = AtomicUsize::new(0); // bytes in use
+static PEAK: AtomicUsize = AtomicUsize::new(0);
+
+/// The system allocator, counting the allocations while a measure is running
+pub struct Counting;
+
+impl Counting {
+    fn allocated(size: usize) {
+        ALLOCATIONS.fetch_add(1, Ordering::Relaxed);
+        BYTES.fetch_add(size, Ordering::Relaxed);
+        let current = CURRENT.fetch_add(size, Ordering::Relaxed) + size;
+        PEAK.fetch_max(current, Ordering::Relaxed);
+    }
+
+    fn freed(size: usize) {
+        // memory allocated before the measure started may be freed during it
+        let _ = CURRENT.fetch_update(Ordering::Relaxed, Ordering::Relaxed, |current| Some(current.saturating_sub(size)));
+    }
+}
+
+unsafe impl GlobalAlloc for Counting {
+    unsafe fn alloc(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.load(Ordering::Relaxed) {
+            Counting::allocated(layout.size());
+        }
+        System.alloc(layout)
+    }
+
+    unsafe fn alloc_zeroed(&self, layout: Layout) -> *mut u8 {
+        if COUNTING.load(Ordering::Relaxed) {
+            Counting::allocated(layout.size());
+        }
+        System.alloc_zeroed(layout)
+    }
+
+    unsafe fn dealloc(&self, ptr: *mut u8, layout: Layout) {
+        if COUNTING.load(Ordering::Relaxed) {
+            Counting::freed(layout.size());
+        }
+        System.dealloc(ptr, layout)
+    }
+
+    unsafe fn realloc(&self, ptr: *mut u8, layout: Layout, new_size: usize) -> *mut u8 {
+        if COUNTING.load(Ordering::Relaxed) {
+            // counted as a new allocation, the old one being freed
+            Counting::freed(layout.size());
+            Counting::allocated(new_size);
+        }
+        System.realloc(ptr, layout, new_size)
+    }
+}
+
+/// The memory used by a step
+#[derive(Debug, Default, Serialize)]
+pub struct Usage {
+    /// Nb of allocations, reallocations included
+    pub allocations: usize,
+    /// Total nb of bytes allocated
+    pub bytes: usize,
+    /// Max nb of bytes in use at once, on top of what was in use before the step
+    pub peak: usize,
+}
+
+// Run `f`, counting the memory it allocates
+fn measure<R>(f: impl FnOnce() -> R) -> (R, Usage) {
+    ALLOCATIONS.store(0, Ordering::Relaxed);
+    BYTES.store(0, Ordering::Relaxed);
+    CURRENT.store(0, Ordering::Relaxed);
+    PEAK.store(0, Ordering::Relaxed);
+    COUNTING.store(true, Ordering::SeqCst);
+    let result = black_box(f());
+    COUNTING.store(false, Ordering::SeqCst);
+    let usage = Usage {
+        allocations: ALLOCATIONS.load(Ordering::Relaxed),
+        bytes: BYTES.load(Ordering::Relaxed),
+        peak: PEAK.load(Ordering::Relaxed),
+    };
+    (result, usage)
+}
+
+/// The memory used by each step of a day
+#[derive(Serialize)]
+pub struct DayUsage {
+    pub day: u8,
+    pub parse: Usage,
+    pub part1: Usage,
+    pub part2: Usage,
+}
+
+impl DayUsage {
+    fn steps(&self) -> [(&'static str, &Usage); 3] {
+        [("parse", &self.parse), ("part 1", &self.part1), ("part 2", &self.part2)]
+    }
+}
+
+/// Count separately the memory allocated by the parsing and both parts of a day.
+/// The answers are dropped within each part, so that the memory they hold is freed within the part too.
+pub fn measure_day<S: Solution>(day: u8, input: &str) -> Result<DayUsage, ParseError> {
+    let (parsed, parse) = measure(|| S::parse(black_box(input)));
+    let parsed = parsed?;
+    let (_, part1) = measure(|| {
+        black_box(S::part1(&parsed));
+    });
+    let (_, part2) = measure(|| {
+        black_box(S::part2(&parsed));
+    });
+    Ok(DayUsage { day, parse, part1, part2 })
+}
+
+fn bytes(n: usize) -> String {
+    match n {
+        n if n >= 1 << 30 => format!("{:.1} GiB", n as f64 / (1u64 << 30) as f64),
+        n if n >= 1 << 20 => format!("{:.1} MiB", n as f64 / (1u64 << 20) as f64),
+        n if n >= 1 << 10 => format!("{:.1} KiB", n as f64 / (1u64 << 10) as f64),
+        n => format!("{} B", n),
+    }
+}
+
+/// One line per step: allocations, bytes allocated and peak memory
+pub fn print_day(day_usage: &DayUsage) {
+    for (step, usage) in day_usage.steps().iter() {
+        println!(
+            "Day {:>2} {:<6}  allocations {:>10}  allocated {:>10}  peak {:>10}",
+            day_usage.day,
+            step,
+            usage.allocations,
+            bytes(usage.bytes),
+            bytes(usage.peak)
+        );
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn counts_allocations() {
+        // the other tests may allocate at the same time: only lower bounds hold
+        let (_, usage) = measure(|| {
+            let mut v: Vec<u64> = Vec::with_capacity(1000);
+            v.push(1);
+            black_box(v);
+        });
+        assert!(usage.allocations >= 1);
+        assert!(usage.bytes >= 8000);
+        assert!(usage.peak >= 8000);
+    }
+}
@@ -0,0 +1,109 @@
+use crate::days::{self, Day, DAYS};
+use proptest::prelude::*;
+use std::fs;
+
+// The parts to solve: part 2 of day 15 takes seconds
+fn parts(day: &Day) -> &'static [u8] {
+    if day.number == 15 { &[1] } else { &[1, 2] }
+}
+
+// Whatever the input, `check` finds an error exactly when parsing fails: never a panic
+fn check_agrees_with_parse(day: &Day, input: &str) -> Result<(), TestCaseError> {
+    let errors = day.check(input, "-");
+    // once parsed, the parts are solved too: they must not panic either
+    let parsed = day.solve(parts(day), input, "-");
+    prop_assert_eq!(errors.is_empty(), parsed.is_ok(), "day {}: {:?} / {:?}", day.number, errors, parsed);
+    Ok(())
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    // the inputs of every day, cut anywhere
+    #[test]
+    fn truncated_inputs(day in 0..DAYS.len(), cut in 0.0..1.0f64) {
+        let day = &DAYS[day];
+        let input = fs::read_to_string(day.default_input()).unwrap();
+        let mut end = (input.len() as f64 * cut) as usize;
+        while !input.is_char_boundary(end) {
+            end -= 1;
+        }
+        check_agrees_with_parse(day, &input[..end])?;
+    }
+
+    // made of the characters found in the inputs of every day
+    #[test]
+    fn garbage_inputs(day in 0..DAYS.len(), input in "[0-9a-zFBLRXx#.L:,()+*=\\[\\] \\r\\n-]{0,100}") {
+        check_agrees_with_parse(&DAYS[day], &input)?;
+    }
+}
+
+// Numbers at the limits of the integer types the days parse into, or just past them
+const LARGE: &[&str] = &[
+    "2147483647",
+    "4294967295",
+    "68719476735",
+    "99999999999",
+    "9223372036854775807",
+    "18446744073709551615",
+    "18446744073709551616",
+];
+
+// Replace some numbers of `input` by large ones: the n-th number by the n-th pick (cycling
+// through them), unless it is None
+fn with_large_numbers(input: &str, picks: &[Option<usize>]) -> String {
+    let mut replaced = String::with_capacity(input.len());
+    let mut picks = picks.iter().cycle();
+    let mut rest = input;
+    while let Some(start) = rest.find(|c: char| c.is_ascii_digit()) {
+        let end = rest[start..].find(|c: char| !c.is_ascii_digit()).map_or(rest.len(), |len| start + len);
+        replaced += &rest[..start];
+        match picks.next().copied().flatten() {
+            Some(pick) => replaced += LARGE[pick],
+            None => replaced += &rest[start..end],
+        }
+        rest = &rest[end..];
+    }
+    replaced + rest
+}
+
+// bags containing each other: solving would never end
+#[test]
+fn cyclic_bag_rules() {
+    let input = "shiny gold bags contain 1 dark red bag.\ndark red bags contain 2 shiny gold bags.\n";
+    check_agrees_with_parse(days::find(7).unwrap(), input).unwrap();
+    assert!(days::find(7).unwrap().solve(&[1, 2], input, "-").is_err());
+}
+
+proptest! {
+    #![proptest_config(ProptestConfig::with_cases(64))]
+
+    // the generated inputs of every day, some of their numbers being large
+    #[test]
+    fn large_numbers(
+        day in 0..DAYS.len(),
+        seed: u64,
+        picks in prop::collection::vec(prop::option::weighted(0.3, 0..LARGE.len()), 1..50),
+    ) {
+        let day = &DAYS[day];
+        let input = day.generate(10, seed).unwrap();
+        check_agrees_with_parse(day, &with_large_numbers(&input, &picks))?;
+    }
+
+    // the homework of day 18 only has digits: its values get large with long expressions
+    #[test]
+    fn long_expressions(terms in prop::collection::vec((1..10u8, prop_oneof![Just('+'), Just('*')]), 1..60)) {
+        let expr = terms.iter().map(|(digit, op)| format!("{} {} ", digit, op)).collect::<String>() + "9";
+        check_agrees_with_parse(days::find(18).unwrap(), &expr)?;
+    }
+
+    // valid, and solvable
+    #[test]
+    fn generated_inputs(day in 0..DAYS.len(), size in 1..20usize, seed: u64) {
+        let day = &DAYS[day];
+        let input = day.generate(size.max(*day.sizes().start()), seed).unwrap();
+        let errors = day.check(&input, "-");
+        prop_assert!(errors.is_empty(), "day {}: {:?} in\n{}", day.number, errors, input);
+        prop_assert!(day.solve(parts(day), &input, "-").is_ok());
+    }
+}
@@ -0,0 +1,140 @@
+use serde::{Deserialize, Serialize};
+use std::env;
+use std::fs;
+use std::path::PathBuf;
+
+// Where the answers are stored, unless the environment variable `AOC_ANSWERS` tells otherwise
+const DEFAULT_STORE: &str = ".answers.json";
+
+/// An answer, as given the first time a part was solved on some input
+#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+pub struct Entry {
+    pub day: u8,
+    pub part: u8,
+    /// Hash of the puzzle input
+    pub input: String,
+    /// The input file the answer was first given for, to help finding it again
+    pub file: String,
+    pub answer: String,
+    /// Set once the answer is known to be correct
+    pub verified: bool,
+}
+
+/// What became of an answer once compared with the store
+#[derive(Debug, PartialEq)]
+pub enum Outcome {
+    /// First time this part is solved on this input: the answer is now stored
+    New,
+    /// Same answer as the stored one
+    Same { verified: bool },
+    /// Not the stored answer! The store is left as it was.
+    Changed { stored: Entry },
+}
+
+/// The answers given so far, by day, part and input
+pub struct Store {
+    path: PathBuf,
+    entries: Vec<Entry>,
+}
+
+/// Hash of a puzzle input, identifying it in the store
+pub fn hash(input: &str) -> String {
+    format!("{:x}", md5::compute(input.as_bytes()))
+}
+
+impl Store {
+    /// The store file: `$AOC_ANSWERS`, or `.answers.json` at the root of the workspace
+    pub fn default_path() -> PathBuf {
+        match env::var_os("AOC_ANSWERS") {
+            Some(path) => PathBuf::from(path),
+            None => PathBuf::from(env!("CARGO_MANIFEST_DIR")).join("..").join(DEFAULT_STORE),
+        }
+    }
+
+    /// Load the store, empty if the file doesn't exist yet
+    pub fn load(path: PathBuf) -> Result<Store, String> {
+        if !path.exists() {
+            return Ok(Store { path, entries: Vec::new() });
+        }
+        let json = fs::read_to_string(&path).map_err(|e| format!("Error in reading file {}: {}", path.display(), e))?;
+        let entries = serde_json::from_str(&json).map_err(|e| format!("{}: invalid answer store: {}", path.display(), e))?;
+        Ok(Store { path, entries })
+    }
+
+    /// Write the store back to its file
+    pub fn save(&self) -> Result<(), String> {
+        let json = serde_json::to_string_pretty(&self.entries).unwrap();
+        // write aside, then rename: an interrupted write doesn't lose the store
+        let tmp = self.path.with_extension("tmp");
+        fs::write(&tmp, json + "\n")
+            .and_then(|_| fs::rename(&tmp, &self.path))
+            .map_err(|e| format!("Error in writing file {}: {}", self.path.display(), e))
+    }
+
+    /// The stored answers, by day, part and input
+    pub fn entries(&self) -> &[Entry] {
+        &self.entries
+    }
+
+    /// Compare an answer with the stored one, storing it if there is none yet
+    pub fn record(&mut self, day: u8, part: u8, input: &str, file: &str, answer: &str) -> Outcome {
+        match self.entries.iter().find(|e| e.day == day && e.part == part && e.input == input) {
+            Some(stored) if stored.answer == answer => Outcome::Same { verified: stored.verified },
+            Some(stored) => Outcome::Changed { stored: stored.clone() },
+            None => {
+                let entry = Entry {
+                    day,
+                    part,
+                    input: input.to_string(),
+                    file: file.to_string(),
+                    answer: answer.to_string(),
+                    verified: false,
+                };
+                let at = self.entries.partition_point(|e| (e.day, e.part, &e.input) < (day, part, &entry.input));
+                self.entries.insert(at, entry);
+                Outcome::New
+            }
+        }
+    }
+
+    /// Mark the stored answers of `day` on `input` as correct, for `part` or both parts.
+    /// Return the nb of answers marked.
+    pub fn verify(&mut self, day: u8, part: Option<u8>, input: &str) -> usize {
+        let mut marked = 0;
+        for entry in self.entries.iter_mut().filter(|e| e.day == day && part.is_none_or(|p| e.part == p) && e.input == input) {
+            entry.verified = true;
+            marked += 1;
+        }
+        marked
+    }
+
+    /// Remove the stored answers of `day` on `input`, for `part` or both parts, so that
+    /// the next run stores them again. Return the nb of answers removed.
+    pub fn forget(&mut self, day: u8, part: Option<u8>, input: &str) -> usize {
+        let before = self.entries.len();
+        self.entries.retain(|e| !(e.day == day && part.is_none_or(|p| e.part == p) && e.input == input));
+        before - self.entries.len()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn changed_answers() {
+        let mut store = Store { path: PathBuf::new(), entries: Vec::new() };
+        let input = hash("1721\n979\n");
+        assert_eq!(Outcome::New, store.record(1, 2, &input, "input", "241861950"));
+        assert_eq!(Outcome::New, store.record(1, 1, &input, "input", "514579"));
+        assert_eq!(Outcome::Same { verified: false }, store.record(1, 1, &input, "other", "514579"));
+        assert_eq!(1, store.verify(1, Some(1), &input));
+        let stored = store.entries()[0].clone();
+        assert_eq!((1, true), (stored.part, stored.verified));
+        assert_eq!(Outcome::Changed { stored }, store.record(1, 1, &input, "input", "42"));
+        // an answer on another input is another answer
+        assert_eq!(Outcome::New, store.record(1, 1, &hash("1721\n"), "small", "0"));
+        assert_eq!(2, store.forget(1, None, &input));
+        assert_eq!(1, store.entries().len());
+    }
+}
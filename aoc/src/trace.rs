@@ -0,0 +1,177 @@
+use aoc_common::Frame;
+use std::borrow::Cow;
+use std::collections::BTreeSet;
+use std::convert::TryFrom;
+use std::fs::{self, File};
+use std::io::{self, BufWriter, Write};
+use std::path::Path;
+use std::str::FromStr;
+
+const SCALE: usize = 4; // pixels per cell of the maps
+const DELAY: u16 = 10; // between two images of a GIF, in hundredths of second
+const PADDING: char = ' '; // around the maps smaller than the largest one
+
+/// How the recorded steps are exported
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum TraceFormat {
+    /// A block of text per step: its label, its state and its map
+    Text,
+    /// A JSON object per step, one per line
+    Json,
+    /// A PPM image per step, drawing its map
+    Ppm,
+    /// An animated GIF, drawing the maps one after the other
+    Gif,
+}
+
+impl FromStr for TraceFormat {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<TraceFormat, String> {
+        match s {
+            "text" => Ok(TraceFormat::Text),
+            "json" => Ok(TraceFormat::Json),
+            "ppm" => Ok(TraceFormat::Ppm),
+            "gif" => Ok(TraceFormat::Gif),
+            _ => Err(format!("Invalid format `{}`, expecting text, json, ppm or gif", s)),
+        }
+    }
+}
+
+/// Write each frame as a block of text, followed by an empty line
+pub fn write_text(frames: &[Frame], w: &mut impl Write) -> io::Result<()> {
+    for frame in frames {
+        writeln!(w, "step {}: {}", frame.step, frame.label)?;
+        for (name, value) in &frame.state {
+            writeln!(w, "  {} = {}", name, value)?;
+        }
+        write!(w, "{}", frame.map)?;
+        writeln!(w)?;
+    }
+    Ok(())
+}
+
+/// Write each frame as a single line JSON object
+pub fn write_json(frames: &[Frame], w: &mut impl Write) -> io::Result<()> {
+    for frame in frames {
+        writeln!(w, "{}", serde_json::to_string(frame)?)?;
+    }
+    Ok(())
+}
+
+/// Draw the map of each frame into its own PPM image in `dir`: frame-00000.ppm, frame-00001.ppm...
+pub fn write_ppm(frames: &[Frame], dir: &Path) -> Result<(), String> {
+    let pictures = Pictures::new(frames)?;
+    fs::create_dir_all(dir).map_err(|e| format!("Error in creating directory {}: {}", dir.display(), e))?;
+    for (step, cells) in &pictures.images {
+        let f_name = dir.join(format!("frame-{:05}.ppm", step));
+        let write = || -> io::Result<()> {
+            let mut w = BufWriter::new(File::create(&f_name)?);
+            write!(w, "P6\n{} {}\n255\n", pictures.width, pictures.height)?;
+            for &cell in cells {
+                w.write_all(&color(pictures.palette[usize::from(cell)]))?;
+            }
+            w.flush()
+        };
+        write().map_err(|e| format!("Error in writing file {}: {}", f_name.display(), e))?;
+    }
+    Ok(())
+}
+
+/// Draw the maps of the frames one after the other into an animated GIF
+pub fn write_gif(frames: &[Frame], f_name: &Path) -> Result<(), String> {
+    let pictures = Pictures::new(frames)?;
+    let too_large = || format!("The maps are too large for a GIF: {}x{} pixels", pictures.width, pictures.height);
+    let width = u16::try_from(pictures.width).map_err(|_| too_large())?;
+    let height = u16::try_from(pictures.height).map_err(|_| too_large())?;
+    let palette: Vec<u8> = pictures.palette.iter().flat_map(|&c| color(c)).collect();
+    let write = || -> Result<(), gif::EncodingError> {
+        let mut encoder = gif::Encoder::new(BufWriter::new(File::create(f_name)?), width, height, &palette)?;
+        encoder.set_repeat(gif::Repeat::Infinite)?;
+        for (_, cells) in &pictures.images {
+            let image = gif::Frame { width, height, delay: DELAY, buffer: Cow::Borrowed(cells), ..gif::Frame::default() };
+            encoder.write_frame(&image)?;
+        }
+        Ok(())
+    };
+    write().map_err(|e| format!("Error in writing file {}: {}", f_name.display(), e))
+}
+
+// The maps of the frames, drawn as images of the same size.
+// Each pixel is the index in `palette` of the character it comes from.
+struct Pictures {
+    width: usize,
+    height: usize,
+    palette: Vec<char>,
+    images: Vec<(usize, Vec<u8>)>, // step, pixels row after row
+}
+
+impl Pictures {
+    fn new(frames: &[Frame]) -> Result<Pictures, String> {
+        let frames: Vec<&Frame> = frames.iter().filter(|f| !f.map.is_empty()).collect();
+        if frames.is_empty() {
+            return Err("There is no map to draw: export the steps as text or json instead".to_string());
+        }
+        let mut chars: BTreeSet<char> = frames.iter().flat_map(|f| f.map.chars()).filter(|&c| c != '\n').collect();
+        chars.insert(PADDING);
+        if chars.len() > 256 {
+            return Err(format!("Too many different characters in the maps to draw them: {}", chars.len()));
+        }
+        let palette: Vec<char> = chars.into_iter().collect();
+        let index = |c: char| palette.binary_search(&c).unwrap() as u8;
+
+        let cols = frames.iter().flat_map(|f| f.map.lines()).map(|row| row.chars().count()).max().unwrap_or(0);
+        let rows = frames.iter().map(|f| f.map.lines().count()).max().unwrap_or(0);
+        let (width, height) = (cols * SCALE, rows * SCALE);
+        let images = frames
+            .iter()
+            .map(|frame| {
+                let mut pixels = vec![index(PADDING); width * height];
+                for (y, row) in frame.map.lines().enumerate() {
+                    for (x, c) in row.chars().enumerate() {
+                        for dy in 0..SCALE {
+                            let start = (y * SCALE + dy) * width + x * SCALE;
+                            pixels[start..start + SCALE].iter_mut().for_each(|p| *p = index(c));
+                        }
+                    }
+                }
+                (frame.step, pixels)
+            })
+            .collect();
+        Ok(Pictures { width, height, palette, images })
+    }
+}
+
+// The color of a character of the maps
+fn color(c: char) -> [u8; 3] {
+    match c {
+        PADDING => [0x00, 0x00, 0x00],
+        '.' => [0x20, 0x20, 0x30], // floor, open squares
+        '#' => [0x2e, 0x8b, 0x57], // trees, occupied seats
+        'L' => [0xc0, 0xc0, 0xc0], // empty seats
+        'O' => [0xff, 0xd7, 0x00], // the toboggan on an open square
+        'X' => [0xe0, 0x30, 0x30], // the toboggan on a tree
+        _ => {
+            // any other character gets a color of its own
+            let n = c as u32;
+            [(n * 67) as u8, (n * 131) as u8, (n * 197) as u8]
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn maps_of_different_sizes() {
+        let frames = vec![Frame::new("a").with_map("#.\n".to_string()), Frame::new("b"), Frame::new("c").with_map("#\n.#\n".to_string())];
+        let pictures = Pictures::new(&frames).unwrap();
+        assert_eq!((2 * SCALE, 2 * SCALE), (pictures.width, pictures.height));
+        assert_eq!(vec![' ', '#', '.'], pictures.palette);
+        // the frame without a map is skipped, the smaller map is padded
+        assert_eq!(2, pictures.images.len());
+        assert_eq!(&[1, 1, 1, 1, 2, 2, 2, 2], &pictures.images[0].1[..2 * SCALE]);
+        assert_eq!(0, pictures.images[0].1[2 * SCALE * SCALE * 2 - 1]);
+    }
+}
@@ -0,0 +1,290 @@
+use crate::days::{Day, DAYS};
+use aoc_common::{Frame, Recorder};
+use ratatui::crossterm::event::{self, Event, KeyCode, KeyEventKind};
+use ratatui::layout::{Constraint, Layout, Rect};
+use ratatui::style::{Style, Stylize};
+use ratatui::text::{Line, Text};
+use ratatui::widgets::{Block, List, ListState, Paragraph};
+use ratatui::DefaultTerminal;
+use std::fs;
+use std::path::PathBuf;
+
+const TRACE_LIMIT: usize = 10_000; // steps recorded at most, to keep the memory in check
+const PAGE: usize = 10; // steps skipped by PageUp / PageDown
+
+const BROWSE_HELP: &str = "↑↓ select  ←→/Tab switch list  1/2 run a part  Enter run both  t/T step through part 1/2  q quit";
+const REPLAY_HELP: &str = "←→ step  PgUp/PgDn 10 steps  Home/End first/last  ↑↓ scroll  Esc back";
+
+#[derive(PartialEq)]
+enum Focus {
+    Days,
+    Inputs,
+}
+
+// The recorded steps of a part, and the one on display
+struct Replay {
+    title: String,
+    frames: Vec<Frame>,
+    dropped: usize,
+    at: usize,
+    scroll: u16,
+}
+
+struct App {
+    days: ListState,
+    inputs: Vec<String>, // file names in the directory of the selected day
+    input: ListState,
+    focus: Focus,
+    results: Vec<String>, // newest last
+    replay: Option<Replay>,
+    quit: bool,
+}
+
+/// The puzzle inputs found in the directory of a day: `input.txt`, `example.txt`, `example2.txt`...
+pub fn list_inputs(day: &Day) -> Vec<String> {
+    let dir = day.default_input().parent().unwrap().to_path_buf();
+    let mut inputs: Vec<String> = match fs::read_dir(&dir) {
+        Ok(entries) => entries
+            .filter_map(|entry| entry.ok())
+            .filter(|entry| entry.path().is_file())
+            .filter_map(|entry| entry.file_name().into_string().ok())
+            .filter(|name| name.starts_with("input") || name.starts_with("example"))
+            .collect(),
+        Err(_) => Vec::new(),
+    };
+    inputs.sort();
+    inputs
+}
+
+/// Browse the days and their inputs, solve them and step through the simulations, until `q` is pressed
+pub fn run() -> Result<(), String> {
+    let mut terminal = ratatui::init();
+    let result = App::new().run(&mut terminal);
+    ratatui::restore();
+    result.map_err(|e| format!("Terminal error: {}", e))
+}
+
+impl App {
+    fn new() -> App {
+        let mut app = App {
+            days: ListState::default().with_selected(Some(0)),
+            inputs: Vec::new(),
+            input: ListState::default(),
+            focus: Focus::Days,
+            results: Vec::new(),
+            replay: None,
+            quit: false,
+        };
+        app.select_day(0);
+        app
+    }
+
+    fn run(&mut self, terminal: &mut DefaultTerminal) -> std::io::Result<()> {
+        while !self.quit {
+            terminal.draw(|frame| self.draw(frame))?;
+            if let Event::Key(key) = event::read()? {
+                if key.kind == KeyEventKind::Press {
+                    self.on_key(key.code);
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn day(&self) -> &'static Day {
+        &DAYS[self.days.selected().unwrap_or(0)]
+    }
+
+    fn select_day(&mut self, index: usize) {
+        self.days.select(Some(index));
+        self.inputs = list_inputs(self.day());
+        // the actual puzzle input first, when there is one
+        let default = self.day().default_input();
+        let default = default.file_name().and_then(|f| f.to_str()).unwrap_or("");
+        let selected = self.inputs.iter().position(|f| f == default).or(if self.inputs.is_empty() { None } else { Some(0) });
+        self.input.select(selected);
+    }
+
+    // The selected input file, with its path
+    fn input_file(&self) -> Option<(String, PathBuf)> {
+        let name = self.inputs.get(self.input.selected()?)?;
+        Some((name.clone(), self.day().default_input().with_file_name(name)))
+    }
+
+    fn on_key(&mut self, key: KeyCode) {
+        if self.replay.is_some() {
+            self.on_replay_key(key);
+            return;
+        }
+        match key {
+            KeyCode::Char('q') | KeyCode::Esc => self.quit = true,
+            KeyCode::Tab | KeyCode::Left | KeyCode::Right => {
+                self.focus = if self.focus == Focus::Days { Focus::Inputs } else { Focus::Days };
+            }
+            KeyCode::Up | KeyCode::Down => {
+                let up = key == KeyCode::Up;
+                if self.focus == Focus::Days {
+                    let selected = self.days.selected().unwrap_or(0);
+                    let selected = if up { selected.saturating_sub(1) } else { (selected + 1).min(DAYS.len() - 1) };
+                    self.select_day(selected);
+                } else if up {
+                    self.input.select_previous();
+                } else {
+                    self.input.select_next();
+                }
+            }
+            KeyCode::Char('1') => self.solve(&[1]),
+            KeyCode::Char('2') => self.solve(&[2]),
+            KeyCode::Enter => self.solve(&[1, 2]),
+            KeyCode::Char('t') => self.trace(1),
+            KeyCode::Char('T') => self.trace(2),
+            _ => (),
+        }
+    }
+
+    fn on_replay_key(&mut self, key: KeyCode) {
+        let replay = self.replay.as_mut().unwrap();
+        let last = replay.frames.len().saturating_sub(1);
+        match key {
+            KeyCode::Esc | KeyCode::Char('q') => self.replay = None,
+            KeyCode::Right => replay.at = (replay.at + 1).min(last),
+            KeyCode::Left => replay.at = replay.at.saturating_sub(1),
+            KeyCode::PageDown => replay.at = (replay.at + PAGE).min(last),
+            KeyCode::PageUp => replay.at = replay.at.saturating_sub(PAGE),
+            KeyCode::Home => replay.at = 0,
+            KeyCode::End => replay.at = last,
+            KeyCode::Down => replay.scroll = replay.scroll.saturating_add(1),
+            KeyCode::Up => replay.scroll = replay.scroll.saturating_sub(1),
+            _ => (),
+        }
+    }
+
+    // Read the selected input, or report why it can't be
+    fn read_input(&mut self) -> Option<(String, String)> {
+        let (name, path) = match self.input_file() {
+            Some(file) => file,
+            None => {
+                self.results.push(format!("Day {}: no input to solve", self.day().number));
+                return None;
+            }
+        };
+        match aoc_common::read_file(&path.display().to_string()) {
+            Ok(contents) => Some((name, contents)),
+            Err(e) => {
+                self.results.push(e.to_string());
+                None
+            }
+        }
+    }
+
+    fn solve(&mut self, parts: &[u8]) {
+        let day = self.day();
+        let (name, contents) = match self.read_input() {
+            Some(input) => input,
+            None => return,
+        };
+        match day.solve(parts, &contents, &name) {
+            Ok(answers) => {
+                for answer in answers {
+                    self.results.push(format!("Day {} part {} on {} = {} (after {:?})", day.number, answer.part, name, answer, answer.elapsed()));
+                }
+            }
+            Err(e) => self.results.push(e),
+        }
+    }
+
+    fn trace(&mut self, part: u8) {
+        let day = self.day();
+        let (name, contents) = match self.read_input() {
+            Some(input) => input,
+            None => return,
+        };
+        let mut recorder = Recorder::new(Some(TRACE_LIMIT));
+        match day.trace(part, &contents, &name, &mut recorder) {
+            Ok(()) if recorder.frames.is_empty() => self.results.push(format!("Day {} part {} on {}: no step to show", day.number, part, name)),
+            Ok(()) => {
+                let title = format!("Day {} part {} on {}", day.number, part, name);
+                self.replay = Some(Replay { title, frames: recorder.frames, dropped: recorder.dropped, at: 0, scroll: 0 });
+            }
+            Err(e) => self.results.push(e),
+        }
+    }
+
+    fn draw(&mut self, frame: &mut ratatui::Frame) {
+        let [main, help] = Layout::vertical([Constraint::Min(0), Constraint::Length(1)]).areas(frame.area());
+        if let Some(replay) = &self.replay {
+            frame.render_widget(replay_view(replay), main);
+            frame.render_widget(Line::from(REPLAY_HELP).dim(), help);
+            return;
+        }
+        let [days, inputs, results] =
+            Layout::horizontal([Constraint::Length(10), Constraint::Length(20), Constraint::Min(0)]).areas(main);
+        let highlight = |focused: bool| if focused { Style::new().reversed() } else { Style::new().underlined() };
+
+        let day_names: Vec<String> = DAYS.iter().map(|d| format!("Day {:>2}", d.number)).collect();
+        let list = List::new(day_names).block(Block::bordered().title("Days")).highlight_style(highlight(self.focus == Focus::Days));
+        frame.render_stateful_widget(list, days, &mut self.days);
+
+        let list = List::new(self.inputs.clone())
+            .block(Block::bordered().title("Inputs"))
+            .highlight_style(highlight(self.focus == Focus::Inputs));
+        frame.render_stateful_widget(list, inputs, &mut self.input);
+
+        frame.render_widget(results_view(&self.results, results), results);
+        frame.render_widget(Line::from(BROWSE_HELP).dim(), help);
+    }
+}
+
+// The latest results, as many as fit in `area`
+fn results_view(results: &[String], area: Rect) -> Paragraph<'_> {
+    let visible = (area.height as usize).saturating_sub(2); // the borders
+    let lines: Vec<Line> = results[results.len().saturating_sub(visible)..].iter().map(|r| Line::from(r.as_str())).collect();
+    Paragraph::new(lines).block(Block::bordered().title("Results"))
+}
+
+// A step: what happened, the state, and the map
+fn replay_view(replay: &Replay) -> Paragraph<'_> {
+    let frame = &replay.frames[replay.at];
+    let mut text = Text::from(Line::from(frame.label.as_str()).bold());
+    for (name, value) in &frame.state {
+        text.push_line(format!("{} = {}", name, value));
+    }
+    for row in frame.map.lines() {
+        text.push_line(row);
+    }
+    let mut title = format!("{}: step {} / {}", replay.title, replay.at + 1, replay.frames.len());
+    if replay.dropped > 0 {
+        title += &format!(" ({} more steps not recorded)", replay.dropped);
+    }
+    Paragraph::new(text).block(Block::bordered().title(title)).scroll((replay.scroll, 0))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::days;
+
+    #[test]
+    fn inputs_of_a_day() {
+        assert_eq!(vec!["example", "input"], list_inputs(days::find(1).unwrap()));
+        let inputs = list_inputs(days::find(11).unwrap());
+        assert!(inputs.contains(&"input.txt".to_string()) && inputs.contains(&"example.txt".to_string()));
+        assert!(!inputs.contains(&"answers.txt".to_string()));
+    }
+
+    #[test]
+    fn step_through_a_simulation() {
+        let mut app = App::new();
+        app.select_day(DAYS.iter().position(|d| d.number == 12).unwrap());
+        app.on_key(KeyCode::Char('t'));
+        app.on_key(KeyCode::Right);
+        app.on_key(KeyCode::Right);
+        app.on_key(KeyCode::Left);
+        let replay = app.replay.as_ref().unwrap();
+        assert_eq!((1, 0), (replay.at, replay.dropped));
+        assert_eq!("Day 12 part 1 on input.txt", replay.title);
+        app.on_key(KeyCode::Esc);
+        app.on_key(KeyCode::Char('1'));
+        assert_eq!(Some("Day 12 part 1 on input.txt = 2847"), app.results.last().and_then(|r| r.split(" (after").next()));
+    }
+}
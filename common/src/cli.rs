@@ -0,0 +1,225 @@
+use crate::PartAnswer;
+use serde::Serialize;
+use std::env;
+use std::fmt::{self, Display};
+use std::process;
+use std::str::FromStr;
+use std::time::{Duration, Instant};
+
+const USAGE: &str = "[PATH|-] [--format text|json]";
+
+/// How the answers are printed
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Format {
+    /// A sentence per answer
+    Text,
+    /// A JSON object per answer, one per line
+    Json,
+}
+
+impl FromStr for Format {
+    type Err = String;
+
+    fn from_str(s: &str) -> Result<Format, String> {
+        match s {
+            "text" => Ok(Format::Text),
+            "json" => Ok(Format::Json),
+            _ => Err(format!("Invalid format `{}`, expecting text or json", s)),
+        }
+    }
+}
+
+/// An answer and the time it took to compute it
+#[derive(Debug, Serialize)]
+pub struct Answer {
+    /// Day of the puzzle
+    pub day: u8,
+    /// Part of the puzzle, 1 or 2, or 0 for an answer according to a policy instead
+    pub part: u8,
+    /// The policy of the answer, for the days solving the puzzle with other rules than those of its parts
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub policy: Option<String>,
+    /// The answer itself, `none` when the input has none
+    pub answer: String,
+    /// Why the input has no answer, if so
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub reason: Option<String>,
+    /// Time spent solving the part, in nanoseconds
+    pub elapsed_ns: u64,
+}
+
+impl Answer {
+    /// Solve a part, timing it
+    pub fn solve<A: PartAnswer>(day: u8, part: u8, solve: impl FnOnce() -> A) -> Answer {
+        let start = Instant::now();
+        let solved = solve();
+        let elapsed_ns = start.elapsed().as_nanos() as u64;
+        let (answer, reason) = match solved.reason() {
+            Some(reason) => ("none".to_string(), Some(reason.to_string())),
+            None => (solved.to_string(), None),
+        };
+        Answer { day, part, policy: None, answer, reason, elapsed_ns }
+    }
+
+    /// The same answer, according to the named policy rather than to a part of the puzzle
+    pub fn with_policy(self, policy: &str) -> Answer {
+        Answer { part: 0, policy: Some(policy.to_string()), ..self }
+    }
+
+    /// Time spent solving the part
+    pub fn elapsed(&self) -> Duration {
+        Duration::from_nanos(self.elapsed_ns)
+    }
+
+    /// The answer as a single line JSON object
+    pub fn to_json(&self) -> String {
+        serde_json::to_string(self).unwrap()
+    }
+}
+
+// the answer, and why there is none if so
+impl Display for Answer {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match &self.reason {
+            Some(reason) => write!(f, "{}, {}", self.answer, reason),
+            None => write!(f, "{}", self.answer),
+        }
+    }
+}
+
+/// Command line arguments of the binary of a day: `[PATH|-] [--format text|json]`
+#[derive(Debug, PartialEq)]
+pub struct Args {
+    /// Path of the puzzle input, `-` being the standard input
+    pub input: String,
+    /// How to print the answers
+    pub format: Format,
+}
+
+impl Args {
+    /// Parse the command line, or exit showing the usage
+    pub fn from_env(default_input: &str) -> Args {
+        Args::from_env_with(default_input, "", |name, _| Err(format!("Unexpected argument `{}`", name)))
+    }
+
+    /// Parse the command line, or exit showing the usage, `usage` describing the options of the day.
+    /// Those options, like `--k 3`, are given to `option` along with their value.
+    pub fn from_env_with(
+        default_input: &str,
+        usage: &str,
+        option: impl FnMut(&str, &str) -> Result<(), String>,
+    ) -> Args {
+        let args: Vec<String> = env::args().skip(1).collect();
+        Args::parse_with(&args, default_input, option).unwrap_or_else(|e| {
+            let bin = env::args().next().unwrap_or_default();
+            eprintln!("{}\nUsage: {} {} {}", e, bin, USAGE, usage);
+            process::exit(1);
+        })
+    }
+
+    /// Parse the arguments, not including the name of the binary
+    pub fn parse(args: &[String], default_input: &str) -> Result<Args, String> {
+        Args::parse_with(args, default_input, |name, _| Err(format!("Unexpected argument `{}`", name)))
+    }
+
+    /// Parse the arguments, not including the name of the binary.
+    /// The options other than `--format` are given to `option` along with their value.
+    pub fn parse_with(
+        args: &[String],
+        default_input: &str,
+        mut option: impl FnMut(&str, &str) -> Result<(), String>,
+    ) -> Result<Args, String> {
+        let mut input = None;
+        let mut format = Format::Text;
+        let mut iter = args.iter();
+        while let Some(arg) = iter.next() {
+            match arg.as_str() {
+                "--format" => {
+                    let value = iter.next().ok_or("Missing value for --format")?;
+                    format = value.parse()?;
+                }
+                path if input.is_none() && (path == "-" || !path.starts_with('-')) => {
+                    input = Some(path.to_string());
+                }
+                name if name.starts_with("--") => {
+                    let value = iter.next().ok_or(format!("Missing value for {}", name))?;
+                    option(name, value)?;
+                }
+                _ => return Err(format!("Unexpected argument `{}`", arg)),
+            }
+        }
+        let input = input.unwrap_or_else(|| default_input.to_string());
+        Ok(Args { input, format })
+    }
+
+    /// Solve a part, then print its answer either as JSON, or as the given sentence
+    pub fn report<A: PartAnswer>(
+        &self,
+        day: u8,
+        part: u8,
+        solve: impl FnOnce() -> A,
+        sentence: impl FnOnce(&str) -> String,
+    ) {
+        self.print(&Answer::solve(day, part, solve), sentence);
+    }
+
+    /// Print an answer either as JSON, or as the given sentence
+    pub fn print(&self, answer: &Answer, sentence: impl FnOnce(&str) -> String) {
+        match self.format {
+            Format::Text => println!("{}", sentence(&answer.to_string())),
+            Format::Json => println!("{}", answer.to_json()),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::Solved;
+
+    fn args(args: &[&str]) -> Result<Args, String> {
+        let args: Vec<String> = args.iter().map(|s| s.to_string()).collect();
+        Args::parse(&args, "input.txt")
+    }
+
+    #[test]
+    fn parse_args() {
+        assert_eq!(Ok(Args { input: "input.txt".to_string(), format: Format::Text }), args(&[]));
+        assert_eq!(Ok(Args { input: "-".to_string(), format: Format::Json }), args(&["-", "--format", "json"]));
+        assert_eq!(Ok(Args { input: "x.txt".to_string(), format: Format::Json }), args(&["--format", "json", "x.txt"]));
+        assert!(args(&["--format", "xml"]).is_err());
+        assert!(args(&["a.txt", "b.txt"]).is_err());
+        assert!(args(&["--k", "3"]).is_err());
+    }
+
+    #[test]
+    fn parse_options() {
+        let args: Vec<String> = ["--k", "3", "x.txt"].iter().map(|s| s.to_string()).collect();
+        let mut k = None;
+        let parsed = Args::parse_with(&args, "input.txt", |name, value| {
+            assert_eq!("--k", name);
+            k = Some(value.to_string());
+            Ok(())
+        });
+        assert_eq!(Ok(Args { input: "x.txt".to_string(), format: Format::Text }), parsed);
+        assert_eq!(Some("3".to_string()), k);
+    }
+
+    #[test]
+    fn answer_to_json() {
+        let answer = Answer { day: 21, part: 2, policy: None, answer: "mxmxvkd,sqjhc".to_string(), reason: None, elapsed_ns: 42 };
+        assert_eq!(r#"{"day":21,"part":2,"answer":"mxmxvkd,sqjhc","elapsed_ns":42}"#, answer.to_json());
+        let answer = Answer { day: 2, part: 1, policy: None, answer: "3".to_string(), reason: None, elapsed_ns: 42 }.with_policy("short");
+        assert_eq!(r#"{"day":2,"part":0,"policy":"short","answer":"3","elapsed_ns":42}"#, answer.to_json());
+    }
+
+    #[test]
+    fn no_answer() {
+        // stored as `none`, whatever the reason says
+        let answer = Answer::solve(7, 2, || Solved::<u64>::none("overflow"));
+        assert_eq!(("none", Some("overflow")), (answer.answer.as_str(), answer.reason.as_deref()));
+        assert_eq!("none, overflow", answer.to_string());
+        assert!(answer.to_json().contains(r#""answer":"none","reason":"overflow""#));
+        assert_eq!("42", Answer::solve(7, 1, || Solved::Answer(42)).to_string());
+    }
+}
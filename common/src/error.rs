@@ -0,0 +1,66 @@
+use std::error::Error;
+use std::fmt;
+
+/// Where and why the puzzle input could not be parsed
+#[derive(Debug, Clone, PartialEq)]
+pub struct ParseError {
+    /// File being parsed, when known
+    pub file: Option<String>,
+    /// Line of the error, starting at 1. 0 when the error is not about a given line.
+    pub line: usize,
+    /// Column of the error, starting at 1. 0 when the error is about the whole line.
+    pub column: usize,
+    /// What went wrong
+    pub msg: String,
+}
+
+impl ParseError {
+    /// An error at the given line and column
+    pub fn new(line: usize, column: usize, msg: impl Into<String>) -> ParseError {
+        ParseError {
+            file: None,
+            line,
+            column,
+            msg: msg.into(),
+        }
+    }
+
+    /// Set the line of the error, for errors raised by parsers only seeing a single line
+    pub fn at_line(mut self, line: usize) -> ParseError {
+        self.line = line;
+        self
+    }
+
+    /// Attach the name of the file being parsed (`-` being the standard input)
+    pub fn in_file(mut self, f_name: &str) -> ParseError {
+        if f_name == crate::input::STDIN {
+            self.file = Some("<stdin>".to_string());
+        } else {
+            self.file = Some(f_name.to_string());
+        }
+        self
+    }
+}
+
+impl fmt::Display for ParseError {
+    fn fmt(&self, f: &mut fmt::Formatter) -> fmt::Result {
+        // like compilers do: "input.txt:12:5: expected a number"
+        let mut location = Vec::new();
+        if let Some(file) = &self.file {
+            location.push(file.clone());
+        }
+        if self.line > 0 {
+            location.push(self.line.to_string());
+            if self.column > 0 {
+                location.push(self.column.to_string());
+            }
+        }
+        if location.is_empty() {
+            write!(f, "{}", self.msg)
+        } else {
+            write!(f, "{}: {}", location.join(":"), self.msg)
+        }
+    }
+}
+
+impl Error for ParseError {}
@@ -0,0 +1,123 @@
+use crate::error::ParseError;
+use std::fmt::Display;
+use std::fs;
+use std::io::{self, Read};
+use std::str::FromStr;
+
+/// The file name standing for the standard input
+pub const STDIN: &str = "-";
+
+/// A line of the puzzle input, along with its number
+#[derive(Debug, Clone, Copy)]
+pub struct Line<'a> {
+    /// Line number, starting at 1
+    pub no: usize,
+    /// Content of the line, without the line ending
+    pub text: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Column (starting at 1) where `token` starts, `token` being a slice of this line.
+    /// 0 if it's not.
+    pub fn column_of(&self, token: &str) -> usize {
+        let start = self.text.as_ptr() as usize;
+        let pos = token.as_ptr() as usize;
+        if pos >= start && pos <= start + self.text.len() {
+            pos - start + 1
+        } else {
+            0
+        }
+    }
+
+    /// An error about `token`, a slice of this line
+    pub fn error(&self, token: &str, msg: impl Into<String>) -> ParseError {
+        ParseError::new(self.no, self.column_of(token), msg)
+    }
+
+    /// Parse `token`, a slice of this line, into a number (or anything implementing `FromStr`)
+    pub fn parse<T>(&self, token: &str) -> Result<T, ParseError>
+    where
+        T: FromStr,
+        T::Err: Display,
+    {
+        token
+            .parse::<T>()
+            .map_err(|e| self.error(token, format!("invalid number `{}`: {}", token, e)))
+    }
+}
+
+/// Iterate over the lines of the puzzle input, numbered from 1.
+/// Handles both LF and CRLF line endings, and strips the trailing whitespaces.
+pub fn lines(input: &str) -> impl Iterator<Item = Line<'_>> {
+    input.lines().enumerate().map(|(i, text)| Line {
+        no: i + 1,
+        text: text.trim_end(),
+    })
+}
+
+/// Iterate over the non-blank lines of the puzzle input, for inputs having one record per line
+pub fn records(input: &str) -> impl Iterator<Item = Line<'_>> {
+    lines(input).filter(|l| !l.text.is_empty())
+}
+
+/// Split the puzzle input in groups of lines separated by blank lines.
+/// Consecutive blank lines, and blank lines at the start or at the end of the input,
+/// don't make empty groups.
+pub fn groups(input: &str) -> Vec<Vec<Line<'_>>> {
+    let mut groups = Vec::new();
+    let mut group = Vec::new();
+    for line in lines(input) {
+        if !line.text.is_empty() {
+            group.push(line);
+        } else if !group.is_empty() {
+            groups.push(group);
+            group = Vec::new();
+        }
+    }
+    // ...don't forget the last one, when there is no blank line after it
+    if !group.is_empty() {
+        groups.push(group);
+    }
+    groups
+}
+
+/// Read a whole puzzle input file, or the standard input when `f_name` is `-`
+pub fn read_file(f_name: &str) -> Result<String, ParseError> {
+    let contents = if f_name == STDIN {
+        let mut contents = String::new();
+        io::stdin().read_to_string(&mut contents).map(|_| contents)
+    } else {
+        fs::read_to_string(f_name)
+    };
+    contents
+        .map_err(|e| ParseError::new(0, 0, format!("can't read the file: {}", e)).in_file(f_name))
+}
+
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn texts<'a>(groups: &[Vec<Line<'a>>]) -> Vec<Vec<&'a str>> {
+        groups
+            .iter()
+            .map(|g| g.iter().map(|l| l.text).collect())
+            .collect()
+    }
+
+    #[test]
+    fn groups_ignore_line_endings() {
+        let expected = vec![vec!["a", "b"], vec!["c"]];
+        assert_eq!(expected, texts(&groups("a\nb\n\nc\n")));
+        assert_eq!(expected, texts(&groups("a\r\nb\r\n\r\nc\r\n")));
+        assert_eq!(expected, texts(&groups("a \nb\t\n  \nc")));
+        assert_eq!(expected, texts(&groups("\na\nb\n\n\n\nc\n\n")));
+    }
+
+    #[test]
+    fn groups_keep_line_numbers() {
+        let groups = groups("a\r\n\r\n\r\nb\r\nc");
+        let numbers: Vec<usize> = groups[1].iter().map(|l| l.no).collect();
+        assert_eq!(vec![4, 5], numbers);
+    }
+}
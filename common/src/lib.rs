@@ -0,0 +1,133 @@
+//! Building blocks shared by all the days of the Advent of Code 2020.
+#![warn(missing_docs)]
+
+mod cli;
+mod error;
+mod input;
+mod rng;
+mod trace;
+
+pub use cli::{Answer, Args, Format};
+pub use error::ParseError;
+pub use input::{groups, lines, read_file, records, Line, STDIN};
+pub use rng::Rng;
+pub use trace::{Frame, NoTrace, Recorder, Tracer};
+
+use std::fmt::{self, Display};
+
+/// Every day of the Advent of Code follows the same steps: the puzzle input is parsed once,
+/// then each part computes its answer out of the parsed input.
+pub trait Solution {
+    /// The puzzle input, once parsed
+    type Input;
+    /// Answer of part 1
+    type Answer1: PartAnswer;
+    /// Answer of part 2
+    type Answer2: PartAnswer;
+
+    /// Parse the puzzle input
+    fn parse(input: &str) -> Result<Self::Input, ParseError>;
+    /// Solve part 1
+    fn part1(input: &Self::Input) -> Self::Answer1;
+    /// Solve part 2
+    fn part2(input: &Self::Input) -> Self::Answer2;
+
+    /// Check the puzzle input without solving anything, reporting every error found.
+    /// Only reports the first one, unless the day knows better.
+    fn check(input: &str) -> Vec<ParseError> {
+        Self::parse(input).err().into_iter().collect()
+    }
+}
+
+/// The answer of a part, or the reason why the puzzle input has none
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Solved<T> {
+    /// The answer
+    Answer(T),
+    /// No answer, for the reason given
+    None(String),
+}
+
+impl<T> Solved<T> {
+    /// No answer, for the reason given
+    pub fn none(reason: impl Display) -> Solved<T> {
+        Solved::None(reason.to_string())
+    }
+}
+
+impl<T: Display> Display for Solved<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            Solved::Answer(answer) => write!(f, "{}", answer),
+            Solved::None(reason) => write!(f, "none, {}", reason),
+        }
+    }
+}
+
+/// What a part can answer: a value, printed as is, or a `Solved` that may have no answer.
+/// The answers are stored and compared without the reason, so that rewording it changes nothing.
+pub trait PartAnswer: Display {
+    /// Why there is no answer, if there is none
+    fn reason(&self) -> Option<&str> {
+        None
+    }
+}
+
+impl<T: Display> PartAnswer for Solved<T> {
+    fn reason(&self) -> Option<&str> {
+        match self {
+            Solved::Answer(_) => None,
+            Solved::None(reason) => Some(reason),
+        }
+    }
+}
+
+macro_rules! part_answers {
+    ($($t:ty),*) => { $(impl PartAnswer for $t {})* };
+}
+
+part_answers!(u8, u16, u32, u64, u128, usize, i8, i16, i32, i64, i128, isize, String, &str, num_bigint::BigInt);
+
+/// A day able to make up puzzle inputs of its own, to load-test its solution
+pub trait Generator: Solution {
+    /// A valid puzzle input made of about `size` records (lines, groups, cards... depending on the day).
+    /// When the puzzle needs it, the input has a solution.
+    fn generate(size: usize, rng: &mut Rng) -> String;
+
+    /// The smallest `size` of the inputs made up by `generate`, for the days needing a few records
+    /// to have a solution: asked for less, `generate` makes up that many anyway.
+    const MIN_SIZE: usize = 1;
+
+    /// The largest `size` of the inputs made up by `generate`, for the days having one:
+    /// asked for more, `generate` stops there.
+    const MAX_SIZE: usize = usize::MAX;
+}
+
+/// A day whose solution simulates something step by step, and can report each of those steps
+pub trait Traced: Solution {
+    /// Solve `part` (1 or 2) again, recording each step of the simulation into `tracer`
+    fn trace<T: Tracer>(input: &Self::Input, part: u8, tracer: &mut T);
+}
+
+/// Read and parse a puzzle input file, or the standard input when `f_name` is `-`.
+/// Errors mention the file name.
+pub fn parse_file<S: Solution>(f_name: &str) -> Result<S::Input, ParseError> {
+    let contents = read_file(f_name)?;
+    S::parse(&contents).map_err(|e| e.in_file(f_name))
+}
+
+/// Check the puzzle input of `S` line by line with `parse_line`, reporting every malformed line
+/// instead of stopping at the first one. Blank lines are skipped.
+/// When every line is fine, the whole input is parsed, to catch errors spanning several lines.
+pub fn check_records<S, T, F>(input: &str, mut parse_line: F) -> Vec<ParseError>
+where
+    S: Solution,
+    F: FnMut(&Line) -> Result<T, ParseError>,
+{
+    let errors: Vec<ParseError> = records(input).filter_map(|l| parse_line(&l).err()).collect();
+    if errors.is_empty() {
+        S::parse(input).err().into_iter().collect()
+    } else {
+        errors
+    }
+}
@@ -0,0 +1,95 @@
+use serde::Serialize;
+use serde_json::{Map, Value};
+
+/// The state of a simulation after one of its steps
+#[derive(Debug, Clone, Serialize)]
+pub struct Frame {
+    /// Number of the step, counting from 0. Set by the [`Recorder`]
+    pub step: usize,
+    /// What happened during the step, like "F10" or "round 3"
+    pub label: String,
+    /// Named values describing the state, like the accumulator or the decks
+    pub state: Map<String, Value>,
+    /// The state drawn as a character map, one row per line, for the simulations that can draw it
+    #[serde(skip_serializing_if = "String::is_empty")]
+    pub map: String,
+}
+
+impl Frame {
+    /// A frame without any state yet
+    pub fn new(label: impl Into<String>) -> Frame {
+        Frame { step: 0, label: label.into(), state: Map::new(), map: String::new() }
+    }
+
+    /// Add a named value to the state
+    pub fn with(mut self, name: &str, value: impl Serialize) -> Frame {
+        let value = serde_json::to_value(value).expect("a state value without any map keyed by non strings");
+        self.state.insert(name.to_string(), value);
+        self
+    }
+
+    /// Draw the state as a character map
+    pub fn with_map(mut self, map: String) -> Frame {
+        self.map = map;
+        self
+    }
+}
+
+/// Where a simulation reports its steps
+pub trait Tracer {
+    /// Record a step. `frame` is only called when the step is kept, so that
+    /// an untraced simulation doesn't pay for building the frames.
+    fn record<F: FnOnce() -> Frame>(&mut self, frame: F);
+}
+
+/// Keeps nothing: the simulations run at full speed
+pub struct NoTrace;
+
+impl Tracer for NoTrace {
+    fn record<F: FnOnce() -> Frame>(&mut self, _frame: F) {}
+}
+
+/// Keeps the frames in memory, up to a limit
+#[derive(Debug, Default)]
+pub struct Recorder {
+    /// The frames recorded so far, in order
+    pub frames: Vec<Frame>,
+    /// Nb of steps that were not kept, once the limit was reached
+    pub dropped: usize,
+    limit: Option<usize>,
+}
+
+impl Recorder {
+    /// A recorder keeping the first `limit` frames, or all of them
+    pub fn new(limit: Option<usize>) -> Recorder {
+        Recorder { frames: Vec::new(), dropped: 0, limit }
+    }
+}
+
+impl Tracer for Recorder {
+    fn record<F: FnOnce() -> Frame>(&mut self, frame: F) {
+        if self.limit.is_some_and(|limit| self.frames.len() >= limit) {
+            self.dropped += 1;
+            return;
+        }
+        let mut frame = frame();
+        frame.step = self.frames.len();
+        self.frames.push(frame);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn limit() {
+        let mut recorder = Recorder::new(Some(2));
+        for i in 0..5 {
+            recorder.record(|| Frame::new(format!("step {}", i)).with("i", i));
+        }
+        assert_eq!(vec![0, 1], recorder.frames.iter().map(|f| f.step).collect::<Vec<_>>());
+        assert_eq!(3, recorder.dropped);
+        assert_eq!(r#"{"step":1,"label":"step 1","state":{"i":1}}"#, serde_json::to_string(&recorder.frames[1]).unwrap());
+    }
+}
@@ -0,0 +1,195 @@
+//! Assign each key a distinct value, given the candidate values of every key:
+//! the "which field is which" kind of puzzle.
+//!
+//! Keys left with a single candidate are settled first, and their value is removed
+//! from the candidates of the other keys. When this propagation stalls, the remaining
+//! keys are matched with their values as in a bipartite graph, which tells whether
+//! they have a solution. It is the only one unless a key can be matched again with another value.
+#![warn(missing_docs)]
+
+use std::collections::{BTreeMap, BTreeSet};
+use std::error::Error;
+use std::fmt;
+
+/// Why the keys can't be assigned a single set of values
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum AssignError<K> {
+    /// No assignment gives every key a distinct value.
+    /// Holds the first key found without any candidate left, if any.
+    Unsolvable(Option<K>),
+    /// Several assignments are possible: holds the keys whose value differs between two of them
+    Ambiguous(Vec<K>),
+}
+
+impl<K: fmt::Display> fmt::Display for AssignError<K> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            AssignError::Unsolvable(Some(key)) => write!(f, "no value is left for `{}`", key),
+            AssignError::Unsolvable(None) => write!(f, "the keys can't all get a distinct value"),
+            AssignError::Ambiguous(keys) => {
+                let keys: Vec<String> = keys.iter().map(|k| format!("`{}`", k)).collect();
+                write!(f, "several values are possible for {}", keys.join(", "))
+            }
+        }
+    }
+}
+
+impl<K: fmt::Debug + fmt::Display> Error for AssignError<K> {}
+
+/// Give each key one of its candidate values, no value being given twice.
+/// Fails unless there is exactly one way to do so.
+pub fn assign<K, V>(candidates: BTreeMap<K, BTreeSet<V>>) -> Result<BTreeMap<K, V>, AssignError<K>>
+where
+    K: Ord + Clone,
+    V: Ord + Clone,
+{
+    let mut candidates = candidates;
+    let mut assigned = BTreeMap::new();
+    propagate(&mut candidates, &mut assigned)?;
+    if candidates.is_empty() {
+        return Ok(assigned);
+    }
+
+    // propagation stalled: find a matching of the remaining keys with their candidates,
+    // then tell whether any key can be matched with another value
+    let keys: Vec<(K, BTreeSet<V>)> = candidates.into_iter().collect();
+    let values: Vec<V> = keys.iter().flat_map(|(_, values)| values.iter().cloned()).collect::<BTreeSet<V>>().into_iter().collect();
+    let edges: Vec<Vec<usize>> = keys
+        .iter()
+        .map(|(_, candidates)| candidates.iter().map(|v| values.binary_search(v).unwrap()).collect())
+        .collect();
+    let mut matching = Matching { edges: &edges, key_of: vec![None; values.len()], value_of: vec![None; keys.len()] };
+    for key in 0..keys.len() {
+        if !matching.augment(key, &mut vec![false; values.len()], None) {
+            return Err(AssignError::Unsolvable(None));
+        }
+    }
+    for key in 0..keys.len() {
+        if let Some(other) = matching.rematch(key) {
+            let mut ambiguous: Vec<K> = (0..keys.len())
+                .filter(|&k| matching.value_of[k] != other.value_of[k])
+                .map(|k| keys[k].0.clone())
+                .collect();
+            ambiguous.sort();
+            return Err(AssignError::Ambiguous(ambiguous));
+        }
+    }
+    for (k, (key, _)) in keys.into_iter().enumerate() {
+        assigned.insert(key, values[matching.value_of[k].unwrap()].clone());
+    }
+    Ok(assigned)
+}
+
+/// Settle the keys having a single candidate, until there is none left.
+/// Settled keys are moved from `candidates` to `assigned`.
+fn propagate<K, V>(
+    candidates: &mut BTreeMap<K, BTreeSet<V>>,
+    assigned: &mut BTreeMap<K, V>,
+) -> Result<(), AssignError<K>>
+where
+    K: Ord + Clone,
+    V: Ord + Clone,
+{
+    loop {
+        if let Some((key, _)) = candidates.iter().find(|(_, values)| values.is_empty()) {
+            return Err(AssignError::Unsolvable(Some(key.clone())));
+        }
+        let key = match candidates.iter().find(|(_, values)| values.len() == 1) {
+            Some((key, _)) => key.clone(),
+            None => return Ok(()),
+        };
+        let value = candidates.remove(&key).unwrap().into_iter().next().unwrap();
+        for values in candidates.values_mut() {
+            values.remove(&value);
+        }
+        assigned.insert(key, value);
+    }
+}
+
+/// A matching between the keys and their candidate values, by index
+#[derive(Clone)]
+struct Matching<'a> {
+    edges: &'a [Vec<usize>], // the candidate values of each key
+    key_of: Vec<Option<usize>>,
+    value_of: Vec<Option<usize>>,
+}
+
+impl Matching<'_> {
+    /// Match `key` with one of its candidates not `seen` yet, moving the other keys to other
+    /// values if need be (Kuhn's algorithm). The `banned` key and value can't be matched.
+    fn augment(&mut self, key: usize, seen: &mut [bool], banned: Option<(usize, usize)>) -> bool {
+        for &value in &self.edges[key] {
+            if seen[value] || banned == Some((key, value)) {
+                continue;
+            }
+            seen[value] = true;
+            let free = match self.key_of[value] {
+                None => true,
+                Some(other) => self.augment(other, seen, banned),
+            };
+            if free {
+                self.key_of[value] = Some(key);
+                self.value_of[key] = Some(value);
+                return true;
+            }
+        }
+        false
+    }
+
+    /// Another matching of all the keys, where `key` has another value, if any
+    fn rematch(&self, key: usize) -> Option<Matching<'_>> {
+        let mut other = self.clone();
+        let value = other.value_of[key].take().unwrap();
+        other.key_of[value] = None;
+        let seen = &mut vec![false; self.key_of.len()];
+        if other.augment(key, seen, Some((key, value))) {
+            Some(other)
+        } else {
+            None
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn candidates(table: &[(&'static str, &[u8])]) -> BTreeMap<&'static str, BTreeSet<u8>> {
+        table.iter().map(|(key, values)| (*key, values.iter().cloned().collect())).collect()
+    }
+
+    #[test]
+    fn propagation() {
+        let solved = assign(candidates(&[("row", &[0, 1, 2]), ("class", &[1, 2]), ("seat", &[2])]));
+        let expected: BTreeMap<_, _> = vec![("row", 0), ("class", 1), ("seat", 2)].into_iter().collect();
+        assert_eq!(Ok(expected), solved);
+    }
+
+    #[test]
+    fn ambiguous() {
+        let solved = assign(candidates(&[("a", &[1, 2]), ("b", &[1, 2]), ("c", &[3])]));
+        assert_eq!(Err(AssignError::Ambiguous(vec!["a", "b"])), solved);
+        // propagation stalls, the search finds two solutions
+        let solved = assign(candidates(&[("a", &[1, 2]), ("b", &[2, 3]), ("c", &[1, 3]), ("d", &[1, 4])]));
+        assert_eq!(Err(AssignError::Ambiguous(vec!["a", "b", "c"])), solved);
+    }
+
+    #[test]
+    fn unsolvable() {
+        let solved = assign(candidates(&[("a", &[1]), ("b", &[1]), ("c", &[1, 2])]));
+        assert_eq!(Err(AssignError::Unsolvable(Some("b"))), solved);
+        let solved = assign(candidates(&[("a", &[1, 2]), ("b", &[1, 2]), ("c", &[1, 2])]));
+        assert_eq!(Err(AssignError::Unsolvable(None)), solved);
+    }
+
+    #[test]
+    fn many_keys() {
+        let keys: Vec<String> = (0..40).map(|k| format!("k{:02}", k)).collect();
+        // 40 keys sharing 39 values: no need to try every way to give them
+        let table: BTreeMap<String, BTreeSet<u8>> = keys.iter().map(|k| (k.clone(), (0..39).collect())).collect();
+        assert_eq!(Err(AssignError::Unsolvable(None)), assign(table));
+        // the same keys with a value more: any of them can have any value
+        let table: BTreeMap<String, BTreeSet<u8>> = keys.iter().map(|k| (k.clone(), (0..41).collect())).collect();
+        assert!(matches!(assign(table), Err(AssignError::Ambiguous(keys)) if keys.len() >= 2));
+    }
+}
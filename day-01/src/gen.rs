@@ -0,0 +1,25 @@
+//! Random expense reports
+use crate::Day1;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day1 {
+    // the pair and the triple summing to 2020
+    const MIN_SIZE: usize = 5;
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Two entries summing to 2020, three more summing to 2020 too, and the other entries
+        // below 2020, congruent to 2 modulo 3. As 2020 is congruent to 1, any two of them may
+        // sum to 2020, but no three: the search for the triple can't skip any of them, the worst
+        // case of `k_sums` for this target.
+        // The pair is congruent to 2 as well, the triple to 1, 1 and 2: the only triple summing
+        // to 2020 is this one, or its values at other positions.
+        let pair = 3 * rng.range(133..200) + 2;
+        let (a, b) = (3 * rng.range(100..233) + 1, 3 * rng.range(100..233) + 1);
+        let mut entries = vec![pair, 2020 - pair, a, b, 2020 - a - b];
+        while entries.len() < size {
+            entries.push(3 * rng.range(0..673) + 2);
+        }
+        rng.shuffle(&mut entries);
+        entries.iter().map(|e| format!("{}\n", e)).collect()
+    }
+}
@@ -0,0 +1,286 @@
+//! Day 1: Report Repair
+//!
+//! Find the entries of the expense report that sum to 2020.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, lines, ParseError, Solution, Solved};
+use std::fmt;
+
+mod gen;
+
+/// Parse the expense report: one number per line
+pub fn parse_expenses(contents: &str) -> Result<Vec<i64>, ParseError> {
+    let mut numbers = Vec::new();
+    for line in lines(contents) {
+        let entry = line.text.trim();
+        if !entry.is_empty() {
+            numbers.push(line.parse(entry)?);
+        }
+    }
+    Ok(numbers)
+}
+
+/// The sum looked for in the puzzle
+pub const TARGET: i64 = 2020;
+
+/// `k` entries of the report summing to the target
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct KSum {
+    /// Positions of the entries in the report, counting from 0, in increasing order
+    pub positions: Vec<usize>,
+    /// The entries, in the order of their positions
+    pub values: Vec<i64>,
+}
+
+impl KSum {
+    /// Product of the entries, unless it overflows an `i128`.
+    /// Only the products of more than two entries can overflow.
+    pub fn product(&self) -> Solved<i128> {
+        let product = self.values.iter().try_fold(1i128, |product, &v| product.checked_mul(i128::from(v)));
+        product.map_or(Solved::none("overflow"), Solved::Answer)
+    }
+}
+
+impl fmt::Display for KSum {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let positions: Vec<String> = self.positions.iter().map(|p| p.to_string()).collect();
+        let values: Vec<String> = self.values.iter().map(|v| v.to_string()).collect();
+        write!(f, "entries {}: {}, product {}", positions.join(", "), values.join(" + "), self.product())
+    }
+}
+
+/// How many sums `k_sums` looks for
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Mode {
+    /// Stop at the first sum found
+    First,
+    /// Every distinct set of positions
+    All,
+}
+
+/// Find `k` entries of the report summing to `target`. Entries at distinct positions
+/// make distinct sums, even when they have the same values.
+pub fn k_sum(numbers: &[i64], k: usize, target: i64) -> Option<KSum> {
+    k_sums(numbers, k, target, Mode::First).pop()
+}
+
+/// Product of the `k` entries of the report summing to `target`, or why there is none
+pub fn k_product(numbers: &[i64], k: usize, target: i64) -> Solved<i128> {
+    match k_sum(numbers, k, target) {
+        Some(sum) => sum.product(),
+        None => Solved::none(format!("no {} entries sum to {}", k, target)),
+    }
+}
+
+/// Find the sets of `k` entries of the report summing to `target`, sorted by positions.
+/// Once the entries are sorted, takes O(n^(k-1)) time at worst for k >= 2, plus the time to
+/// list the sums found: the last two entries of a sum are found by two pointers in O(n).
+pub fn k_sums(numbers: &[i64], k: usize, target: i64, mode: Mode) -> Vec<KSum> {
+    let mut sorted: Vec<(i64, usize)> = numbers.iter().cloned().zip(0..).collect();
+    sorted.sort_unstable();
+    let mut search = Search { sorted: &sorted, mode, chosen: Vec::with_capacity(k), found: Vec::new() };
+    // the sums of entries can't overflow an i128, even with a billion of them
+    search.find(0, k, i128::from(target));
+    let mut found = search.found;
+    found.sort_by(|a, b| a.positions.cmp(&b.positions));
+    found
+}
+
+// The state of the search for the sums
+struct Search<'a> {
+    sorted: &'a [(i64, usize)], // the entries and their positions, by increasing value
+    mode: Mode,
+    chosen: Vec<usize>, // indexes in `sorted` of the entries of the sum being built
+    found: Vec<KSum>,
+}
+
+impl Search<'_> {
+    fn value(&self, i: usize) -> i128 {
+        i128::from(self.sorted[i].0)
+    }
+
+    // Complete the sum being built with `k` entries from `sorted[from..]`, summing to `target`.
+    // Return true once done searching.
+    fn find(&mut self, from: usize, k: usize, target: i128) -> bool {
+        let end = self.sorted.len();
+        match k {
+            0 => target == 0 && self.push(&[]),
+            1 => {
+                let lo = from + self.sorted[from..].partition_point(|&(v, _)| i128::from(v) < target);
+                let hi = from + self.sorted[from..].partition_point(|&(v, _)| i128::from(v) <= target);
+                (lo..hi).any(|i| self.push(&[i]))
+            }
+            2 => {
+                // the smallest entry can only pair with smaller entries once the sum is too big, and the opposite
+                let (mut lo, mut hi) = (from, end);
+                while lo + 1 < hi {
+                    let sum = self.value(lo) + self.value(hi - 1);
+                    if sum < target {
+                        lo += 1;
+                    } else if sum > target {
+                        hi -= 1;
+                    } else if self.value(lo) == self.value(hi - 1) {
+                        // all the entries in between are equal: any two of them make the sum
+                        return (lo..hi).any(|a| (a + 1..hi).any(|b| self.push(&[a, b])));
+                    } else {
+                        // every entry equal to the smallest one pairs with every entry equal to the largest one
+                        let lo_end = lo + self.sorted[lo..hi].partition_point(|&(v, _)| v == self.sorted[lo].0);
+                        let hi_start = lo + self.sorted[lo..hi].partition_point(|&(v, _)| v < self.sorted[hi - 1].0);
+                        if (lo..lo_end).any(|a| (hi_start..hi).any(|b| self.push(&[a, b]))) {
+                            return true;
+                        }
+                        lo = lo_end;
+                        hi = hi_start;
+                    }
+                }
+                false
+            }
+            _ if end - from < k => false,
+            _ => {
+                let (k_wide, largest) = (k as i128, self.value(end - 1));
+                for i in from..end {
+                    if self.value(i) * k_wide > target {
+                        break; // the following entries are even larger
+                    }
+                    if self.value(i) + largest * (k_wide - 1) < target {
+                        continue; // too small, even with the largest entries
+                    }
+                    if self.mode == Mode::First && i > from && self.sorted[i - 1].0 == self.sorted[i].0 {
+                        continue; // the same sums as the previous entry
+                    }
+                    self.chosen.push(i);
+                    let done = self.find(i + 1, k - 1, target - self.value(i));
+                    self.chosen.pop();
+                    if done {
+                        return true;
+                    }
+                }
+                false
+            }
+        }
+    }
+
+    // Add a sum made of the chosen entries and `last` ones. Return true once done searching.
+    fn push(&mut self, last: &[usize]) -> bool {
+        let mut entries: Vec<(usize, i64)> =
+            self.chosen.iter().chain(last).map(|&i| (self.sorted[i].1, self.sorted[i].0)).collect();
+        entries.sort_unstable();
+        self.found.push(KSum {
+            positions: entries.iter().map(|&(p, _)| p).collect(),
+            values: entries.iter().map(|&(_, v)| v).collect(),
+        });
+        self.mode == Mode::First
+    }
+}
+
+/// Solution of day 1
+pub struct Day1;
+
+impl Solution for Day1 {
+    type Input = Vec<i64>;
+    type Answer1 = Solved<i128>;
+    type Answer2 = Solved<i128>;
+
+    fn parse(input: &str) -> Result<Vec<i64>, ParseError> {
+        parse_expenses(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day1, _, _>(input, |line| line.parse::<i64>(line.text.trim()))
+    }
+
+    // Product of the two entries that sum to 2020
+    fn part1(numbers: &Vec<i64>) -> Solved<i128> {
+        k_product(numbers, 2, TARGET)
+    }
+
+    // Product of the three entries that sum to 2020
+    fn part2(numbers: &Vec<i64>) -> Solved<i128> {
+        k_product(numbers, 3, TARGET)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Generator, Rng};
+
+    const EXAMPLE: [i64; 6] = [1721, 979, 366, 299, 675, 1456];
+
+    fn values(sum: Option<KSum>) -> Option<Vec<i64>> {
+        sum.map(|sum| {
+            let mut values = sum.values;
+            values.sort_unstable();
+            values
+        })
+    }
+
+    #[test]
+    fn any_nb_of_entries() {
+        assert_eq!(Some(vec![299, 1721]), values(k_sum(&EXAMPLE, 2, 2020)));
+        assert_eq!(Some(vec![366, 675, 979]), values(k_sum(&EXAMPLE, 3, 2020)));
+        assert_eq!(Some(vec![979]), values(k_sum(&EXAMPLE, 1, 979)));
+        assert_eq!(Some(vec![299, 366, 675, 1456]), values(k_sum(&EXAMPLE, 4, 2796)));
+        assert_eq!(Some(vec![]), values(k_sum(&EXAMPLE, 0, 0)));
+        assert_eq!(None, k_sum(&EXAMPLE, 2, 2021));
+        // no pair nor triple sums to 2020
+        let numbers = vec![1, 2, 3];
+        assert_eq!((Solved::none("no 2 entries sum to 2020"), Solved::none("no 3 entries sum to 2020")), (Day1::part1(&numbers), Day1::part2(&numbers)));
+        assert_eq!("none, no 2 entries sum to 2020", Day1::part1(&numbers).to_string());
+    }
+
+    #[test]
+    fn original_positions() {
+        let sum = k_sum(&EXAMPLE, 3, 2020).unwrap();
+        assert_eq!((vec![1, 2, 4], vec![979, 366, 675], Solved::Answer(241861950)), (sum.positions.clone(), sum.values.clone(), sum.product()));
+        // an entry can't be used twice
+        assert_eq!(None, k_sum(&[1010, 1], 2, 2020));
+        assert_eq!(vec![0, 2], k_sum(&[1010, 1, 1010], 2, 2020).unwrap().positions);
+    }
+
+    #[test]
+    fn all_sums() {
+        let positions = |numbers: &[i64], k, target| -> Vec<Vec<usize>> {
+            k_sums(numbers, k, target, Mode::All).into_iter().map(|sum| sum.positions).collect()
+        };
+        assert_eq!(vec![vec![0, 1], vec![0, 3], vec![1, 3], vec![2, 4]], positions(&[5, 5, 2, 5, 8], 2, 10));
+        assert_eq!(vec![vec![0, 2], vec![1, 2], vec![2, 3]], positions(&[1, 1, 9, 1], 2, 10));
+        assert_eq!(vec![vec![0, 1, 2], vec![0, 1, 3], vec![0, 2, 3], vec![1, 2, 3]], positions(&[1, 1, 1, 1], 3, 3));
+        assert_eq!(vec![vec![1, 2, 4]], positions(&EXAMPLE, 3, 2020));
+    }
+
+    #[test]
+    fn large_entries() {
+        let large = [i64::MAX, i64::MIN, 1, i64::MAX - 1];
+        let sum = k_sum(&large, 2, i64::MAX).unwrap();
+        assert_eq!((vec![2, 3], Solved::Answer(i128::from(i64::MAX - 1))), (sum.positions.clone(), sum.product()));
+        // the sum of the entries overflows an i64, their product an i128
+        let sum = k_sum(&large, 3, i64::MAX - 2).unwrap();
+        assert_eq!((vec![0, 1, 3], Solved::none("overflow")), (sum.positions.clone(), sum.product()));
+        assert_eq!(None, k_sum(&large, 2, i64::MIN));
+    }
+
+    #[test]
+    fn million_entries() {
+        // the pairs are found in O(n log n)
+        let numbers = parse_expenses(&Day1::generate(1_000_000, &mut Rng::new(1))).unwrap();
+        let sum = k_sum(&numbers, 2, TARGET).unwrap();
+        assert_eq!(TARGET, sum.values.iter().sum::<i64>());
+        assert!(sum.positions.iter().zip(&sum.values).all(|(&p, &v)| numbers[p] == v));
+    }
+
+    #[test]
+    fn worst_case() {
+        // no triple of generated entries but one sums to 2020: the other entries are all tried
+        let numbers = parse_expenses(&Day1::generate(2_000, &mut Rng::new(1))).unwrap();
+        let sums = k_sums(&numbers, 3, TARGET, Mode::All);
+        assert!(!sums.is_empty());
+        let mut values = sums[0].values.clone();
+        values.sort_unstable();
+        assert!(sums.iter().all(|sum| {
+            let mut other = sum.values.clone();
+            other.sort_unstable();
+            other == values
+        }));
+    }
+}
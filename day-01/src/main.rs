@@ -1,35 +1,56 @@
-use std::env;
-use std::fs;
-use std::time::Instant;
+use aoc_common::{parse_file, Args, ParseError, Solution, Solved};
+use day_1::{k_product, k_sums, Day1, Mode, TARGET};
+use std::process;
 
 fn main() {
-    // --snip--
-    let args: Vec<String> = env::args().collect();
-
-    let filename = &args[1];
-
-    println!("Opening file {}", filename);
-
-    let start = Instant::now();
-    let contents = fs::read_to_string(filename)
-        .expect("Something went wrong reading the file");
-
-    let numbers: Vec<i32> = contents
-        .split_whitespace()
-        .map(|s| s.parse().expect("parse error"))
-        .collect();
-
-    for (i, left) in numbers.iter().enumerate() {
-        for (j, mid) in numbers[i..].iter().enumerate() {
-            for (k, right) in numbers[j..].iter().enumerate() {
-                let sum = left + mid + right;
-                if sum == 2020 {
-                    let duration = start.elapsed();
-                    println!("Finished after {:?}", duration);
-                    println!("left[{}] * mid[{}] * right[{}] = {}", i, j, k, left * mid * right);
+    let mut k = None; // both parts when not given
+    let mut target = TARGET;
+    let mut mode = Mode::First;
+    let args = Args::from_env_with("example", "[--k K] [--target T] [--mode first|all]", |name, value| {
+        match name {
+            "--k" => k = Some(value.parse::<usize>().map_err(|_| format!("Invalid nb of entries `{}`", value))?),
+            "--target" => target = value.parse::<i64>().map_err(|_| format!("Invalid target `{}`", value))?,
+            "--mode" => {
+                mode = match value {
+                    "first" => Mode::First,
+                    "all" => Mode::All,
+                    _ => return Err(format!("Invalid mode `{}`, expecting first or all", value)),
                 }
             }
+            _ => return Err(format!("Unexpected argument `{}`", name)),
         }
+        Ok(())
+    });
+    if let Err(e) = run(&args, k, target, mode) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 }
 
+fn run(args: &Args, k: Option<usize>, target: i64, mode: Mode) -> Result<(), ParseError> {
+    let input = parse_file::<Day1>(&args.input)?;
+    if k.is_none() && target == TARGET && mode == Mode::First {
+        args.report(1, 1, || Day1::part1(&input), |answer| format!("Product of the 2 entries summing to 2020 = {}", answer));
+        args.report(1, 2, || Day1::part2(&input), |answer| format!("Product of the 3 entries summing to 2020 = {}", answer));
+        return Ok(());
+    }
+    // the pair then the triple, as parts 1 and 2, unless `k` is given
+    let ks = match k {
+        Some(k) => vec![k],
+        None => vec![2, 3],
+    };
+    for (part, k) in (1..).zip(ks) {
+        if mode == Mode::First {
+            let product = || k_product(&input, k, target);
+            args.report(1, part, product, |answer| format!("Product of the {} entries summing to {} = {}", k, target, answer));
+        } else {
+            // one sum per line
+            let sums = || match k_sums(&input, k, target, mode) {
+                sums if sums.is_empty() => Solved::none(format!("no {} entries sum to {}", k, target)),
+                sums => Solved::Answer(sums.iter().map(|sum| sum.to_string()).collect::<Vec<_>>().join("\n")),
+            };
+            args.report(1, part, sums, |answer| format!("Sets of {} entries summing to {}:\n{}", k, target, answer));
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,22 @@
+//! Random password lists
+use crate::Day2;
+use aoc_common::{Generator, Rng};
+
+const LETTERS: &[u8] = b"abcdefghijklmnopqrstuvwxyz";
+
+impl Generator for Day2 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut list = String::new();
+        for _ in 0..size {
+            let left = rng.range(1..10);
+            let right = rng.range(left + 1..left + 10);
+            let letter = *rng.choose(LETTERS) as char;
+            // favor the letter of the policy, so that some passwords are valid
+            let password: String = (0..rng.range(1..21))
+                .map(|_| if rng.one_in(3) { letter } else { *rng.choose(LETTERS) as char })
+                .collect();
+            list += &format!("{}-{} {}: {}\n", left, right, letter, password);
+        }
+        list
+    }
+}
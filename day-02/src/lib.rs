@@ -0,0 +1,230 @@
+//! Day 2: Password Philosophy
+//!
+//! Count the passwords of a list that are valid according to their policy.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, records, Line, ParseError, Solution};
+
+mod gen;
+pub mod rules;
+
+/// A password policy, like "2-8 q"
+#[derive(Debug)]
+pub struct PwdPolicy {
+    letter: char,
+    left: usize,
+    right: usize,
+}
+
+impl PwdPolicy {
+    /// The letter, like `q`
+    pub fn letter(&self) -> char {
+        self.letter
+    }
+
+    /// The first number, like 2. Never 0
+    pub fn left(&self) -> usize {
+        self.left
+    }
+
+    /// The second number, like 8. Never 0
+    pub fn right(&self) -> usize {
+        self.right
+    }
+}
+
+/// A rule telling which passwords are valid. It may interpret the policy of their line,
+/// or ignore it and check anything else, like forbidden substrings.
+///
+/// Any `Fn(&PwdPolicy, &str) -> bool` is a rule too.
+pub trait Policy {
+    /// Whether `password` is valid, given the policy of its line
+    fn is_valid(&self, policy: &PwdPolicy, password: &str) -> bool;
+}
+
+impl<F: Fn(&PwdPolicy, &str) -> bool> Policy for F {
+    fn is_valid(&self, policy: &PwdPolicy, password: &str) -> bool {
+        self(policy, password)
+    }
+}
+
+/// The policy of the sled rental place: for "2-8 q", the letter must appear between 2 and 8 times
+#[derive(Debug, Clone, Copy)]
+pub struct OldPolicy;
+
+impl Policy for OldPolicy {
+    fn is_valid(&self, policy: &PwdPolicy, password: &str) -> bool {
+        let cnt = password.matches(policy.letter).count();
+        cnt >= policy.left && cnt <= policy.right
+    }
+}
+
+/// The policy of the Toboggan Corporate: for "2-8 q", the letter must appear at exactly
+/// one of the positions 2 and 8 (1-indexed)
+#[derive(Debug, Clone, Copy)]
+pub struct NewPolicy;
+
+impl Policy for NewPolicy {
+    fn is_valid(&self, policy: &PwdPolicy, password: &str) -> bool {
+        // positions are 1-indexed, and may be past the end of the password
+        let l_char = password.chars().nth(policy.left - 1);
+        let r_char = password.chars().nth(policy.right - 1);
+
+        let is_left_ok : bool = l_char == Some(policy.letter);
+        let is_right_ok : bool = r_char == Some(policy.letter);
+        is_left_ok ^ is_right_ok // ^ is a XOR operator
+    }
+}
+
+/// A password, along with the policy it must comply with
+#[derive(Debug)]
+pub struct PwdValidator {
+    policy: PwdPolicy,
+    password: String
+}
+
+impl PwdValidator {
+    /// Build a validator from a line of the list like "2-8 q: qqqqqqqqqq"
+    pub fn create_policy(line: &Line) -> Result<PwdValidator, ParseError> {
+        let (policy, password) = match line.text.find(':') {
+            Some(idx) => (&line.text[..idx], line.text[idx + 1..].trim_start()),
+            None => return Err(line.error(line.text, "expecting `<policy>: <password>`")),
+        };
+        // policy is of like "2-8 q"
+        let left_right_char: Vec<&str> = policy.split_whitespace().collect();
+        if left_right_char.len() != 2 {
+            return Err(line.error(policy, format!("expecting a policy like `2-8 q`, found `{}`", policy)));
+        }
+        let left_right: Vec<&str> = left_right_char[0].split('-').collect();
+        if left_right.len() != 2 {
+            return Err(line.error(left_right_char[0], format!("expecting a range like `2-8`, found `{}`", left_right_char[0])));
+        }
+        let mut ch = left_right_char[1].chars();
+        let letter = match (ch.next(), ch.next()) {
+            (Some(letter), None) => letter,
+            _ => return Err(line.error(left_right_char[1], format!("expecting a single letter, found `{}`", left_right_char[1]))),
+        };
+        let policy = PwdPolicy {
+            letter,
+            left: line.parse(left_right[0])?,
+            right: line.parse(left_right[1])?
+        };
+        if policy.left == 0 || policy.right == 0 {
+            return Err(line.error(left_right_char[0], "positions start at 1"));
+        }
+        // return the object created below
+        Ok(PwdValidator {
+            policy,
+            password: password.to_string()
+        })
+    }
+
+    /// The policy of the line
+    pub fn policy(&self) -> &PwdPolicy {
+        &self.policy
+    }
+
+    /// The password
+    pub fn password(&self) -> &str {
+        &self.password
+    }
+
+    /// Check the password against its policy, interpreted according to `rule`
+    pub fn is_valid(&self, rule: &(impl Policy + ?Sized)) -> bool {
+        rule.is_valid(&self.policy, &self.password)
+    }
+}
+
+/// Load the list of passwords, like "2-8 q: qqqqqqqqqq", one per line
+pub fn parse_lines(contents: &str) -> Result<Vec<PwdValidator>, ParseError> {
+    let mut entries = Vec::new();
+    for line in records(contents) {
+        entries.push(PwdValidator::create_policy(&line)?);
+    }
+    Ok(entries)
+}
+
+/// Count the passwords of the list that are valid according to the given rule
+pub fn count_valid(entries: &[PwdValidator], rule: &(impl Policy + ?Sized)) -> usize {
+    entries.iter().filter(|password| password.is_valid(rule)).count()
+}
+
+/// Solution of day 2
+pub struct Day2;
+
+impl Solution for Day2 {
+    type Input = Vec<PwdValidator>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<PwdValidator>, ParseError> {
+        parse_lines(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day2, _, _>(input, PwdValidator::create_policy)
+    }
+
+    fn part1(entries: &Vec<PwdValidator>) -> usize {
+        count_valid(entries, &OldPolicy)
+    }
+
+    fn part2(entries: &Vec<PwdValidator>) -> usize {
+        count_valid(entries, &NewPolicy)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    // The letter at exactly one of the positions given, rather than two of them
+    struct OneOf(Vec<usize>);
+
+    impl Policy for OneOf {
+        fn is_valid(&self, policy: &PwdPolicy, password: &str) -> bool {
+            self.0.iter().filter(|&&pos| password.chars().nth(pos - 1) == Some(policy.letter())).count() == 1
+        }
+    }
+
+    #[test]
+    fn custom_policies() {
+        // blank lines are skipped
+        let entries = parse_lines("1-3 a: abcde\n\n1-3 b: cdefg\n2-9 c: ccccccccc\n\n").unwrap();
+        assert_eq!(2, count_valid(&entries, &OldPolicy));
+        assert_eq!(1, count_valid(&entries, &NewPolicy));
+        assert_eq!(1, count_valid(&entries, &OneOf(vec![1, 2, 5])));
+        let forbidden = |_: &PwdPolicy, password: &str| !password.contains("cd");
+        assert_eq!(1, count_valid(&entries, &forbidden));
+        // any rule, chosen at run time
+        let rules: Vec<Box<dyn Policy>> = vec![Box::new(OldPolicy), Box::new(forbidden)];
+        assert_eq!(vec![2, 1], rules.iter().map(|rule| count_valid(&entries, rule.as_ref())).collect::<Vec<_>>());
+    }
+
+    proptest! {
+        #[test]
+        fn policies_match_their_definition(left in 0usize..20, right in 0usize..20, letter in "[abc]", password in "[abc]{0,15}") {
+            let text = format!("{}-{} {}: {}", left, right, letter, password);
+            let validator = PwdValidator::create_policy(&Line { no: 1, text: &text });
+            if left == 0 || right == 0 {
+                prop_assert!(validator.is_err());
+            } else {
+                let validator = validator.unwrap();
+                let letter = letter.chars().next().unwrap();
+                let cnt = password.chars().filter(|&c| c == letter).count();
+                prop_assert_eq!(left <= cnt && cnt <= right, validator.is_valid(&OldPolicy));
+                let at = |pos: usize| password.chars().nth(pos - 1) == Some(letter);
+                prop_assert_eq!(at(left) != at(right), validator.is_valid(&NewPolicy));
+            }
+        }
+
+        #[test]
+        fn any_line_is_checked(text in "[0-9a-z: -]{0,20}") {
+            // an error or a policy: never a panic
+            if let Ok(validator) = PwdValidator::create_policy(&Line { no: 1, text: &text }) {
+                validator.is_valid(&NewPolicy);
+            }
+        }
+    }
+}
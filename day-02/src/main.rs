@@ -1,95 +1,38 @@
-use std::env;
-use std::fs;
-use std::time::Instant;
+use aoc_common::{parse_file, read_file, Answer, Args, ParseError, Solution};
+use day_2::rules::parse_rules;
+use day_2::{count_valid, Day2};
+use std::process;
 
-#[derive(Debug)]
-enum PwdPolicyKind {
-    Old,
-    New
-}
-
-#[derive(Debug)]
-struct PwdPolicy {
-    kind: PwdPolicyKind,
-    letter: String,
-    left: usize,
-    right: usize,
-}
-
-#[derive(Debug)]
-struct PwdValidator {
-    policy: PwdPolicy,
-    password: String
-}
-
-impl PwdValidator {
-    fn create_policy(kind: PwdPolicyKind, policy: String, password: String) -> PwdValidator {
-        // policy is of like "2-8 q"
-        let left_right_char: Vec<&str> = policy.split_whitespace().collect();
-        let left_right: Vec<usize> = left_right_char[0].split("-").map(|i| i.parse().expect("parse error")).collect();
-        let ch = left_right_char[1];
-        let policy = PwdPolicy {
-            kind,
-            letter: ch.to_string(),
-            left: left_right[0],
-            right: left_right[1]
-        };
-        // return the object created below
-        PwdValidator {
-            policy,
-            password
-        }
-    }
-    fn is_valid(&self) -> bool {
-        match self.policy.kind {
-            PwdPolicyKind::Old => self._is_valid_old_pol(),
-            PwdPolicyKind::New => self._is_valid_new_pol()
-        }
-    }
-
-    fn _is_valid_old_pol(&self) -> bool {
-        let cnt = self.password.matches(&self.policy.letter).count();
-        return if cnt < self.policy.left || cnt > self.policy.right {
-            false
-        } else {
-            true
+fn main() {
+    let mut policies = None; // the policies of the puzzle when not given
+    let args = Args::from_env_with("example.txt", "[--policies RULES]", |name, value| {
+        match name {
+            "--policies" => policies = Some(value.to_string()),
+            _ => return Err(format!("Unexpected argument `{}`", name)),
         }
-    }
-
-    fn _is_valid_new_pol(&self) -> bool {
-        let l_char = self.password.chars().nth(self.policy.left - 1).unwrap();
-        let r_char = self.password.chars().nth(self.policy.right - 1).unwrap();
-
-        let is_left_ok : bool = l_char.to_string() == self.policy.letter;
-        let is_right_ok : bool = r_char.to_string() == self.policy.letter;
-        is_left_ok ^ is_right_ok // ^ is a XOR operator
+        Ok(())
+    });
+    if let Err(e) = run(&args, policies.as_deref()) {
+        eprintln!("{}", e);
+        process::exit(1);
     }
 }
 
-fn main() {
-    let args: Vec<String> = env::args().collect();
-    let filename = &args[1];
-
-    let start = Instant::now();
-    let contents = fs::read_to_string(filename).expect("Something went wrong reading the file");
-
-    let mut old_pol_valid_cnt = 0;
-    let mut new_pol_valid_cnt = 0;
-    for line in contents.lines() {
-        let policy_pwd: Vec<&str> = line.split(":").collect();
-        let password_old_pol = PwdValidator::create_policy(PwdPolicyKind::Old, policy_pwd[0].to_string(), policy_pwd[1].trim_start().to_string());
-        let password_new_pol = PwdValidator::create_policy(PwdPolicyKind::New, policy_pwd[0].to_string(), policy_pwd[1].trim_start().to_string());
-        //println!{"{:?}", password};
-        if password_old_pol.is_valid() {
-            old_pol_valid_cnt += 1;
-        }
-        if password_new_pol.is_valid() {
-            new_pol_valid_cnt += 1;
+fn run(args: &Args, policies: Option<&str>) -> Result<(), ParseError> {
+    let input = parse_file::<Day2>(&args.input)?;
+    let f_name = match policies {
+        Some(f_name) => f_name,
+        None => {
+            args.report(2, 1, || Day2::part1(&input), |answer| format!("Valid password according to *OLD* policy = {}", answer));
+            args.report(2, 2, || Day2::part2(&input), |answer| format!("Valid password according to *NEW* policy = {}", answer));
+            return Ok(());
         }
+    };
+    let rules = read_file(f_name).and_then(|contents| parse_rules(&contents)).map_err(|e| e.in_file(f_name))?;
+    // in the order of the file, not as parts of the puzzle but named by their policy
+    for rule in &rules {
+        let answer = Answer::solve(2, 0, || count_valid(&input, &rule.rule)).with_policy(&rule.name);
+        args.print(&answer, |answer| format!("Valid password according to the `{}` policy = {}", rule.name, answer));
     }
-    println!("Valid password according to *OLD* policy = {}", old_pol_valid_cnt);
-    println!("Valid password according to *NEW* policy = {}", new_pol_valid_cnt);
-
-    let duration = start.elapsed();
-    println!("Finished after {:?}", duration);
+    Ok(())
 }
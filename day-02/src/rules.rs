@@ -0,0 +1,360 @@
+//! Policies described in a rule file, one per line, like:
+//!
+//! ```text
+//! # the policies of the puzzle
+//! old = count in left-right
+//! new = at(left) xor at(right)
+//! # a password of 8 to 20 characters, without any `x`, with the letter first or last
+//! strict = length in 8-20 and not count('x') in 1-99 and (at(1) or at(right))
+//! ```
+//!
+//! The checks are:
+//! - `count in A-B`: the letter of the policy appears between A and B times, `count('x') in A-B` for `x`
+//! - `length in A-B`: the password has between A and B characters
+//! - `at(P)`: the letter of the policy is at the position P (1-indexed), `at(P, 'x')` for `x`
+//!
+//! A, B and P are numbers, or `left` and `right` for the numbers of the policy ("2-8 q").
+//! The checks combine with `not`, `and`, `xor` and `or`, from the highest precedence to the lowest,
+//! and with parentheses.
+use crate::{Policy, PwdPolicy};
+use aoc_common::{lines, Line, ParseError};
+
+/// A number of a rule
+#[derive(Debug, Clone, Copy, PartialEq)]
+pub enum Bound {
+    /// A number given by the rule
+    Number(usize),
+    /// The first number of the policy of the line
+    Left,
+    /// The second number of the policy of the line
+    Right,
+}
+
+impl Bound {
+    fn value(self, policy: &PwdPolicy) -> usize {
+        match self {
+            Bound::Number(n) => n,
+            Bound::Left => policy.left(),
+            Bound::Right => policy.right(),
+        }
+    }
+}
+
+/// A rule, as described in a rule file. Letters not given stand for the letter of the policy.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Rule {
+    /// The letter appears between `min` and `max` times
+    Count {
+        /// The letter counted
+        letter: Option<char>,
+        /// The fewest times it may appear
+        min: Bound,
+        /// The most times it may appear
+        max: Bound,
+    },
+    /// The password has between `min` and `max` characters
+    Length {
+        /// The fewest characters
+        min: Bound,
+        /// The most characters
+        max: Bound,
+    },
+    /// The letter is at the position (1-indexed)
+    At {
+        /// The position, never 0
+        pos: Bound,
+        /// The letter expected there
+        letter: Option<char>,
+    },
+    /// The rule is not followed
+    Not(Box<Rule>),
+    /// Both rules are followed
+    And(Box<Rule>, Box<Rule>),
+    /// At least one of the rules is followed
+    Or(Box<Rule>, Box<Rule>),
+    /// Exactly one of the rules is followed
+    Xor(Box<Rule>, Box<Rule>),
+}
+
+impl Policy for Rule {
+    fn is_valid(&self, policy: &PwdPolicy, password: &str) -> bool {
+        let letter = |letter: &Option<char>| letter.unwrap_or_else(|| policy.letter());
+        match self {
+            Rule::Count { letter: l, min, max } => {
+                let cnt = password.chars().filter(|&c| c == letter(l)).count();
+                cnt >= min.value(policy) && cnt <= max.value(policy)
+            }
+            Rule::Length { min, max } => {
+                let len = password.chars().count();
+                len >= min.value(policy) && len <= max.value(policy)
+            }
+            // positions are never 0
+            Rule::At { pos, letter: l } => password.chars().nth(pos.value(policy) - 1) == Some(letter(l)),
+            Rule::Not(rule) => !rule.is_valid(policy, password),
+            Rule::And(a, b) => a.is_valid(policy, password) && b.is_valid(policy, password),
+            Rule::Or(a, b) => a.is_valid(policy, password) || b.is_valid(policy, password),
+            Rule::Xor(a, b) => a.is_valid(policy, password) ^ b.is_valid(policy, password),
+        }
+    }
+}
+
+/// A rule of a rule file, and the name it's given
+#[derive(Debug, Clone, PartialEq)]
+pub struct NamedRule {
+    /// The name, like `old`
+    pub name: String,
+    /// The rule
+    pub rule: Rule,
+}
+
+/// Load a rule file: one `<name> = <rule>` per line, the blank lines and the lines starting with `#` being skipped
+pub fn parse_rules(contents: &str) -> Result<Vec<NamedRule>, ParseError> {
+    let mut rules: Vec<NamedRule> = Vec::new();
+    for line in lines(contents) {
+        if line.text.trim().is_empty() || line.text.trim_start().starts_with('#') {
+            continue;
+        }
+        let mut parser = Parser::new(line)?;
+        let name = parser.next("a name")?;
+        if !is_word(name) {
+            return Err(line.error(name, format!("expecting a name, found `{}`", name)));
+        }
+        if rules.iter().any(|r| r.name == name) {
+            return Err(line.error(name, format!("rule `{}` already defined", name)));
+        }
+        parser.expect("=")?;
+        let rule = parser.rule()?;
+        if let Some(&token) = parser.tokens.get(parser.at) {
+            return Err(line.error(token, format!("expecting `and`, `or` or `xor`, found `{}`", token)));
+        }
+        rules.push(NamedRule { name: name.to_string(), rule });
+    }
+    Ok(rules)
+}
+
+fn is_word(token: &str) -> bool {
+    token.chars().all(|c| c.is_ascii_alphanumeric() || c == '_')
+}
+
+// Split a line in words, numbers, letters like 'x', and symbols
+fn tokens<'a>(line: &Line<'a>) -> Result<Vec<&'a str>, ParseError> {
+    let text = line.text;
+    let mut tokens = Vec::new();
+    let mut chars = text.char_indices().peekable();
+    while let Some((start, c)) = chars.next() {
+        let mut end = start + c.len_utf8();
+        if c.is_whitespace() {
+            continue;
+        } else if c.is_ascii_alphanumeric() || c == '_' {
+            while let Some(&(i, c)) = chars.peek() {
+                if !(c.is_ascii_alphanumeric() || c == '_') {
+                    break;
+                }
+                end = i + c.len_utf8();
+                chars.next();
+            }
+        } else if c == '\'' {
+            match (chars.next(), chars.next()) {
+                (Some(_), Some((i, '\''))) => end = i + 1,
+                _ => return Err(line.error(&text[start..], "expecting a letter like 'x'")),
+            }
+        } else if !"()-,=".contains(c) {
+            return Err(line.error(&text[start..end], format!("unexpected character `{}`", c)));
+        }
+        tokens.push(&text[start..end]);
+    }
+    Ok(tokens)
+}
+
+// A recursive descent parser over the tokens of a line
+struct Parser<'a> {
+    line: Line<'a>,
+    tokens: Vec<&'a str>,
+    at: usize,
+}
+
+impl<'a> Parser<'a> {
+    fn new(line: Line<'a>) -> Result<Parser<'a>, ParseError> {
+        Ok(Parser { line, tokens: tokens(&line)?, at: 0 })
+    }
+
+    fn peek(&self) -> Option<&'a str> {
+        self.tokens.get(self.at).cloned()
+    }
+
+    fn next(&mut self, expected: &str) -> Result<&'a str, ParseError> {
+        match self.peek() {
+            Some(token) => {
+                self.at += 1;
+                Ok(token)
+            }
+            None => Err(self.line.error(&self.line.text[self.line.text.len()..], format!("expecting {}, found the end of line", expected))),
+        }
+    }
+
+    fn expect(&mut self, expected: &str) -> Result<(), ParseError> {
+        let token = self.next(&format!("`{}`", expected))?;
+        if token != expected {
+            return Err(self.line.error(token, format!("expecting `{}`, found `{}`", expected, token)));
+        }
+        Ok(())
+    }
+
+    // rule := xor { "or" xor }
+    fn rule(&mut self) -> Result<Rule, ParseError> {
+        let mut rule = self.xor()?;
+        while self.peek() == Some("or") {
+            self.at += 1;
+            rule = Rule::Or(Box::new(rule), Box::new(self.xor()?));
+        }
+        Ok(rule)
+    }
+
+    // xor := and { "xor" and }
+    fn xor(&mut self) -> Result<Rule, ParseError> {
+        let mut rule = self.and()?;
+        while self.peek() == Some("xor") {
+            self.at += 1;
+            rule = Rule::Xor(Box::new(rule), Box::new(self.and()?));
+        }
+        Ok(rule)
+    }
+
+    // and := check { "and" check }
+    fn and(&mut self) -> Result<Rule, ParseError> {
+        let mut rule = self.check()?;
+        while self.peek() == Some("and") {
+            self.at += 1;
+            rule = Rule::And(Box::new(rule), Box::new(self.check()?));
+        }
+        Ok(rule)
+    }
+
+    // check := "not" check | "(" rule ")" | "count" [ "(" letter ")" ] "in" range
+    //        | "length" "in" range | "at" "(" bound [ "," letter ] ")"
+    fn check(&mut self) -> Result<Rule, ParseError> {
+        let token = self.next("a check")?;
+        match token {
+            "not" => Ok(Rule::Not(Box::new(self.check()?))),
+            "(" => {
+                let rule = self.rule()?;
+                self.expect(")")?;
+                Ok(rule)
+            }
+            "count" => {
+                let letter = if self.peek() == Some("(") {
+                    self.at += 1;
+                    let letter = self.letter()?;
+                    self.expect(")")?;
+                    Some(letter)
+                } else {
+                    None
+                };
+                self.expect("in")?;
+                let (min, max) = self.range()?;
+                Ok(Rule::Count { letter, min, max })
+            }
+            "length" => {
+                self.expect("in")?;
+                let (min, max) = self.range()?;
+                Ok(Rule::Length { min, max })
+            }
+            "at" => {
+                self.expect("(")?;
+                let pos = self.bound()?;
+                if pos == Bound::Number(0) {
+                    return Err(self.line.error(self.tokens[self.at - 1], "positions start at 1"));
+                }
+                let letter = if self.peek() == Some(",") {
+                    self.at += 1;
+                    Some(self.letter()?)
+                } else {
+                    None
+                };
+                self.expect(")")?;
+                Ok(Rule::At { pos, letter })
+            }
+            _ => Err(self.line.error(token, format!("expecting `count`, `length`, `at`, `not` or `(`, found `{}`", token))),
+        }
+    }
+
+    // range := bound "-" bound
+    fn range(&mut self) -> Result<(Bound, Bound), ParseError> {
+        let min = self.bound()?;
+        self.expect("-")?;
+        Ok((min, self.bound()?))
+    }
+
+    // bound := number | "left" | "right"
+    fn bound(&mut self) -> Result<Bound, ParseError> {
+        let token = self.next("a number")?;
+        match token {
+            "left" => Ok(Bound::Left),
+            "right" => Ok(Bound::Right),
+            _ if token.starts_with(|c: char| c.is_ascii_digit()) => Ok(Bound::Number(self.line.parse(token)?)),
+            _ => Err(self.line.error(token, format!("expecting a number, `left` or `right`, found `{}`", token))),
+        }
+    }
+
+    // letter := "'" char "'"
+    fn letter(&mut self) -> Result<char, ParseError> {
+        let token = self.next("a letter")?;
+        match token.strip_prefix('\'').and_then(|t| t.strip_suffix('\'')) {
+            Some(letter) if letter.chars().count() == 1 => Ok(letter.chars().next().unwrap()),
+            _ => Err(self.line.error(token, format!("expecting a letter like 'x', found `{}`", token))),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::{parse_lines, NewPolicy, OldPolicy, PwdValidator};
+    use proptest::prelude::*;
+
+    const PUZZLE: &str = "# the policies of the puzzle\nold = count in left-right\n\nnew = at(left) xor at(right)\n";
+
+    #[test]
+    fn precedence() {
+        let rules = parse_rules("r = not at(1) and at(2, 'b') or count('é') in 0-0 xor length in 3-right").unwrap();
+        let at = |pos, letter| Box::new(Rule::At { pos: Bound::Number(pos), letter });
+        let and = Rule::And(Box::new(Rule::Not(at(1, None))), at(2, Some('b')));
+        let count = Rule::Count { letter: Some('é'), min: Bound::Number(0), max: Bound::Number(0) };
+        let xor = Rule::Xor(Box::new(count), Box::new(Rule::Length { min: Bound::Number(3), max: Bound::Right }));
+        assert_eq!(vec![NamedRule { name: "r".to_string(), rule: Rule::Or(Box::new(and), Box::new(xor)) }], rules);
+    }
+
+    #[test]
+    fn errors() {
+        let error = |rules: &str| parse_rules(rules).unwrap_err();
+        assert_eq!(ParseError::new(2, 17, "positions start at 1"), error("a = at(1)\nb = at(1) or at(0)"));
+        assert_eq!(ParseError::new(1, 27, "expecting `)`, found the end of line"), error("a = (count in 1-2 or at(3)"));
+        assert_eq!(ParseError::new(1, 16, "expecting a number, `left` or `right`, found `x`"), error("a = count in 1-x"));
+        assert_eq!(ParseError::new(1, 11, "expecting `and`, `or` or `xor`, found `at`"), error("a = at(1) at(2)"));
+        assert_eq!(ParseError::new(2, 1, "rule `a` already defined"), error("a = at(1)\na = at(2)"));
+        assert_eq!(ParseError::new(1, 11, "expecting a letter like 'x'"), error("a = count('xy') in 1-2"));
+        assert_eq!(ParseError::new(1, 7, "unexpected character `+`"), error("a = at+(1)"));
+    }
+
+    proptest! {
+        #[test]
+        fn puzzle_rules_match_the_policies(left in 1usize..20, right in 1usize..20, letter in "[abc]", password in "[abc]{0,15}") {
+            let rules = parse_rules(PUZZLE).unwrap();
+            let text = format!("{}-{} {}: {}", left, right, letter, password);
+            let validator = PwdValidator::create_policy(&Line { no: 1, text: &text }).unwrap();
+            prop_assert_eq!(validator.is_valid(&OldPolicy), validator.is_valid(&rules[0].rule));
+            prop_assert_eq!(validator.is_valid(&NewPolicy), validator.is_valid(&rules[1].rule));
+        }
+
+        #[test]
+        fn any_rule_is_checked(text in "[a-z0-9()', =-]{0,30}") {
+            // an error or rules: never a panic, even when evaluated
+            if let Ok(rules) = parse_rules(&text) {
+                let entries = parse_lines("1-3 a: abcde").unwrap();
+                for rule in rules {
+                    entries[0].is_valid(&rule.rule);
+                }
+            }
+        }
+    }
+}
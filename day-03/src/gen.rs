@@ -0,0 +1,20 @@
+//! Random maps of the slope
+use crate::Day3;
+use aoc_common::{Generator, Rng};
+
+const WIDTH: usize = 31;
+
+impl Generator for Day3 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // `size` rows; the map repeats to the right
+        let mut map = String::new();
+        for y in 0..size.max(1) {
+            for x in 0..WIDTH {
+                let tree = (x, y) != (0, 0) && rng.one_in(5);
+                map.push(if tree { '#' } else { '.' });
+            }
+            map.push('\n');
+        }
+        map
+    }
+}
@@ -0,0 +1,171 @@
+//! Day 3: Toboggan Trajectory
+//!
+//! Count the trees hit when sliding down a repeating map of open squares (`.`) and trees (`#`).
+#![warn(missing_docs)]
+
+use aoc_common::{Frame, NoTrace, ParseError, Solution, Traced, Tracer};
+use aoc_grid::Grid;
+use aoc_math::big::BigInt;
+
+mod gen;
+
+const SLOPE_X: i32 = 3;
+const SLOPE_Y: i32 = 1;
+
+pub use aoc_grid::Xy;
+
+// the slopes tried in part 2, the second one being the slope of part 1
+const SLOPES: [Xy; 5] = [Xy{x:1, y:1}, Xy{x:SLOPE_X, y:SLOPE_Y}, Xy{x:5, y:1}, Xy{x:7, y:1}, Xy{x:1, y:2}];
+
+// The squares of the map, and how to read them: true for a tree
+const SQUARES: &str = "`.` or `#`";
+fn square(c: char) -> Option<bool> {
+    match c {
+        '#' => Some(true),
+        '.' => Some(false),
+        _ => None
+    }
+}
+
+/// The map, and the toboggan going down on it
+#[derive(Clone)]
+pub struct Slope {
+    grid: Grid<bool>, // true where there is a tree
+    santa: Xy,
+    /// Move of the toboggan at each step
+    pub delta: Xy,
+    /// Nb of trees hit since the top of the map
+    pub trees_hit: u64
+}
+
+impl Slope {
+    /// Load the map, the toboggan starting at the top left corner
+    pub fn from_lines (str_in: &str) -> Result<Slope, ParseError> {
+        let grid = Grid::parse(str_in, SQUARES, square)?;
+        Ok(Slope {
+            grid,
+            santa: Xy {x: 0, y: 0},
+            delta: Xy {x: SLOPE_X, y: SLOPE_Y },
+            trees_hit: 0
+        })
+    }
+
+    /// Slide down to the bottom of the map, counting the trees hit
+    pub fn descend (&mut self) {
+        self.descend_traced(&mut NoTrace);
+    }
+
+    /// Slide down to the bottom of the map, counting the trees hit, and recording each move
+    pub fn descend_traced<T: Tracer>(&mut self, tracer: &mut T) {
+        // the map repeats itself to the right
+        while self.santa.y + self.delta.y < self.grid.height() as i32 {
+            self.santa = self.santa + self.delta;
+            let tree = *self.grid.get_wrapping(self.santa);
+            if tree {
+                self.trees_hit += 1;
+            }
+            tracer.record(|| Frame::new(format!("slope ({}, {}): {} at ({}, {})", self.delta.x, self.delta.y, if tree { "tree" } else { "open" }, self.santa.x, self.santa.y))
+                .with("x", self.santa.x)
+                .with("y", self.santa.y)
+                .with("trees_hit", self.trees_hit)
+                .with_map(self.draw()));
+        }
+    }
+
+    /// The map, the path of the toboggan so far marked with `O` on open squares and `X` on trees
+    pub fn draw(&self) -> String {
+        let width = self.grid.width() as i32;
+        let path = |pos: Xy| {
+            // the toboggan went through every multiple of `delta` down to its position
+            pos.y <= self.santa.y && pos.y % self.delta.y == 0 && pos.x == (pos.y / self.delta.y * self.delta.x) % width
+        };
+        let mut map = String::new();
+        for y in 0..self.grid.height() as i32 {
+            for x in 0..width {
+                let pos = Xy { x, y };
+                map.push(match (path(pos), self.grid[pos]) {
+                    (true, true) => 'X',
+                    (true, false) => 'O',
+                    (false, true) => '#',
+                    (false, false) => '.',
+                });
+            }
+            map.push('\n');
+        }
+        map
+    }
+
+    /// Put the toboggan back at the top left corner
+    pub fn reset (&mut self) {
+        self.santa = Xy {x:0, y:0};
+        self.trees_hit = 0;
+    }
+
+}
+
+/// Solution of day 3
+pub struct Day3;
+
+impl Solution for Day3 {
+    type Input = Slope;
+    type Answer1 = u64;
+    type Answer2 = BigInt;
+
+    fn parse(input: &str) -> Result<Slope, ParseError> {
+        Slope::from_lines(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        Grid::check(input, SQUARES, square)
+    }
+
+    fn part1(slope: &Slope) -> u64 {
+        let mut tobogan = slope.clone();
+        tobogan.descend();
+        tobogan.trees_hit
+    }
+
+    fn part2(slope: &Slope) -> BigInt {
+        // the product of the 5 counts overflows a u64 past 7,000 trees hit on each slope
+        let mut tobogan = slope.clone();
+        let mut result = BigInt::from(1);
+        for s in SLOPES.iter() {
+            tobogan.delta = *s;
+            tobogan.descend();
+            result *= tobogan.trees_hit;
+            tobogan.reset();
+        }
+        result
+    }
+}
+
+impl Traced for Day3 {
+    fn trace<T: Tracer>(slope: &Slope, part: u8, tracer: &mut T) {
+        let slopes = if part == 1 { &SLOPES[1..2] } else { &SLOPES[..] };
+        for s in slopes {
+            let mut tobogan = slope.clone();
+            tobogan.delta = *s;
+            tobogan.descend_traced(tracer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn trees_everywhere() {
+        // a tree hit at each step, past the u16 counts and the u64 product
+        let slope = Day3::parse(&"#\n".repeat(70_000)).unwrap();
+        assert_eq!(69_999, Day3::part1(&slope));
+        let every_row = BigInt::from(69_999u64);
+        assert_eq!(&every_row * &every_row * &every_row * &every_row * 34_999u64, Day3::part2(&slope));
+    }
+
+    #[test]
+    fn check_every_row() {
+        let errors: Vec<String> = Day3::check("..#\n.x.\n#.\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["2:2: unexpected `x`, expecting `.` or `#`", "3:1: expecting 3 cells, found 2"], errors);
+    }
+}
@@ -0,0 +1,48 @@
+//! Random batches of passports
+use crate::Day4;
+use aoc_common::{Generator, Rng};
+
+const EYE_COLORS: &[&str] = &["amb", "blu", "brn", "gry", "grn", "hzl", "oth", "xry", "zzz"];
+
+// A value of the field, valid most of the time
+fn value(key: &str, rng: &mut Rng) -> String {
+    let valid = !rng.one_in(4);
+    match (key, valid) {
+        ("byr", true) => rng.range(1920..2003).to_string(),
+        ("iyr", true) => rng.range(2010..2021).to_string(),
+        ("eyr", true) => rng.range(2020..2031).to_string(),
+        ("byr", false) | ("iyr", false) | ("eyr", false) => rng.range(1900..2100).to_string(),
+        ("hgt", true) if rng.one_in(2) => format!("{}cm", rng.range(150..194)),
+        ("hgt", true) => format!("{}in", rng.range(59..77)),
+        ("hgt", false) => format!("{}{}", rng.range(40..250), rng.choose(&["cm", "in", ""])),
+        ("hcl", true) => format!("#{:06x}", rng.range(0..0x100_0000)),
+        ("hcl", false) => format!("{:06x}", rng.range(0..0x100_0000)),
+        ("ecl", true) => rng.choose(&EYE_COLORS[..7]).to_string(),
+        ("ecl", false) => rng.choose(&EYE_COLORS[7..]).to_string(),
+        ("pid", true) => format!("{:09}", rng.range(0..1_000_000_000)),
+        ("pid", false) => format!("{}", rng.range(0..100_000_000_000)),
+        _ => rng.range(10..350).to_string(),
+    }
+}
+
+impl Generator for Day4 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut batch = Vec::new();
+        for _ in 0..size {
+            let mut keys = vec!["byr", "iyr", "eyr", "hgt", "hcl", "ecl", "pid", "cid"];
+            rng.shuffle(&mut keys);
+            // some fields are missing
+            while rng.one_in(5) {
+                keys.pop();
+            }
+            // up to 3 fields per line
+            let mut passport = String::new();
+            for (i, key) in keys.iter().enumerate() {
+                let separator = if i == 0 { "" } else if rng.one_in(3) { "\n" } else { " " };
+                passport += &format!("{}{}:{}", separator, key, value(key, rng));
+            }
+            batch.push(passport + "\n");
+        }
+        batch.join("\n")
+    }
+}
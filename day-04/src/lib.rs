@@ -0,0 +1,174 @@
+//! Day 4: Passport Processing
+//!
+//! Count the passports having the required fields, then the ones whose fields are valid.
+#![warn(missing_docs)]
+
+use aoc_common::{groups, lines, records, Line, ParseError, Solution};
+
+mod gen;
+
+/// The fields of a passport, empty when missing
+#[derive(Debug, Default)]
+pub struct Passport {
+    byr:String, //(Birth Year)
+    iyr:String, //(Issue Year)
+    eyr:String, //(Expiration Year)
+    hgt:String, //(Height)
+    hcl:String, //(Hair Color)
+    ecl:String, //(Eye Color)
+    pid:String, //(Passport ID)
+    cid:String, //(Country ID)
+}
+
+impl Passport {
+    /// Check that all the required fields are present (`cid` is optional)
+    pub fn is_valid_1(&self) -> bool {
+        !self.byr.is_empty() &&
+            !self.iyr.is_empty() &&
+            !self.eyr.is_empty() &&
+            !self.hgt.is_empty() &&
+            !self.hcl.is_empty() &&
+            !self.ecl.is_empty() &&
+            !self.pid.is_empty()
+            // && !self.cid.is_empty() // ignore cid
+    }
+
+    /// Check that all the required fields are present and valid
+    pub fn is_valid_2(&self) -> bool {
+        // byr (Birth Year) - four digits; at least 1920 and at most 2002.
+        self.byr.len() == 4 && self.byr.parse::<i32>().is_ok_and(|y| (1920..=2002).contains(&y)) &&
+            // iyr (Issue Year) - four digits; at least 2010 and at most 2020.
+            self.iyr.len() == 4 && self.iyr.parse::<i32>().is_ok_and(|y| (2010..=2020).contains(&y)) &&
+            // eyr (Expiration Year) - four digits; at least 2020 and at most 2030.
+            self.eyr.len() == 4 && self.eyr.parse::<i32>().is_ok_and(|y| (2020..=2030).contains(&y)) &&
+            self.is_hgt_valid() &&
+            self.is_hcl_valid() &&
+            self.is_ecl_valid() &&
+            self.is_pid_valid()
+            // cid (Country ID) - ignored, missing or not.
+    }
+
+    fn is_hgt_valid(&self) -> bool {
+        // hgt (Height) - a number followed by either cm or in:
+        //   If cm, the number must be at least 150 and at most 193.
+        //   If in, the number must be at least 59 and at most 76.
+        let hgt = &self.hgt;
+        if let Some(h) = hgt.strip_suffix("cm") {
+            if h.parse::<i32>().is_ok_and(|h| (150..=193).contains(&h)) {
+                return true;
+            }
+        }
+        if let Some(h) = hgt.strip_suffix("in") {
+            if h.parse::<i32>().is_ok_and(|h| (59..=76).contains(&h)) {
+                return true;
+            }
+        }
+        false
+    }
+
+    fn is_hcl_valid(&self) -> bool {
+        // hcl (Hair Color) - a # followed by exactly six characters 0-9 or a-f.
+        let hcl = &self.hcl;
+        hcl.len() == 7 && hcl.starts_with('#') && hcl.chars().skip(1).take(5).all(char::is_alphanumeric)
+    }
+
+    fn is_ecl_valid(&self) -> bool {
+        // ecl (Eye Color) - exactly one of: amb blu brn gry grn hzl oth.
+        matches!(self.ecl.as_str(), "amb" | "blu" | "brn" | "gry" | "grn" | "hzl" | "oth")
+    }
+
+    fn is_pid_valid(&self) -> bool {
+        // pid (Passport ID) - a nine-digit number, including leading zeroes.
+        let pid = &self.pid;
+        pid.len() == 9 && pid.chars().all(char::is_numeric)
+    }
+
+    /// Build a passport from its `key:value` fields, separated by whitespaces
+    pub fn from_string(str_in: &str) -> Result<Passport, ParseError> {
+        let mut p:Passport = Passport{..Default::default()};
+        for line in lines(str_in) {
+            p.add_fields(&line)?;
+        }
+        Ok(p)
+    }
+
+    /// Fill the passport with the `key:value` fields of a line of the batch file
+    pub fn add_fields(&mut self, line: &Line) -> Result<(), ParseError> {
+        for f in line.text.split_whitespace() {
+            self.add_field(line, f)?;
+        }
+        Ok(())
+    }
+
+    // Fill the passport with `f`, a `key:value` field of `line`
+    fn add_field(&mut self, line: &Line, f: &str) -> Result<(), ParseError> {
+        let (key, value) = match f.find(':') {
+            Some(idx) => (&f[..idx], f[idx + 1..].to_string()),
+            None => return Err(line.error(f, format!("expecting `key:value`, found `{}`", f)))
+        };
+        match key {
+            "byr" => self.byr = value,
+            "iyr" => self.iyr = value,
+            "eyr" => self.eyr = value,
+            "hgt" => self.hgt = value,
+            "hcl" => self.hcl = value,
+            "ecl" => self.ecl = value,
+            "pid" => self.pid = value,
+            "cid" => self.cid = value,
+            _ => return Err(line.error(f, format!("unknown field `{}`", key)))
+        }
+        Ok(())
+    }
+}
+
+/// Build the list of passports from the batch file: passports are separated by blank lines
+pub fn parse_passports(contents: &str) -> Result<Vec<Passport>, ParseError> {
+    let mut passports= Vec::new();
+    for group in groups(contents) {
+        let mut passport = Passport::default();
+        for line in &group {
+            passport.add_fields(line)?;
+        }
+        passports.push(passport);
+    }
+    Ok(passports)
+}
+
+/// Solution of day 4
+pub struct Day4;
+
+impl Solution for Day4 {
+    type Input = Vec<Passport>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<Passport>, ParseError> {
+        parse_passports(input)
+    }
+
+    // every field on its own: only the fields can be malformed
+    fn check(input: &str) -> Vec<ParseError> {
+        records(input)
+            .flat_map(|line| line.text.split_whitespace().filter_map(move |f| Passport::default().add_field(&line, f).err()))
+            .collect()
+    }
+
+    fn part1(passports: &Vec<Passport>) -> usize {
+        passports.iter().filter(|p| p.is_valid_1()).count()
+    }
+
+    fn part2(passports: &Vec<Passport>) -> usize {
+        passports.iter().filter(|p| p.is_valid_2()).count()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_every_field() {
+        let errors: Vec<String> = Day4::check("byr:1937 iyr\nhgt:183cm\n\nxyz:1 pid:1 abc:2\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["1:10: expecting `key:value`, found `iyr`", "4:1: unknown field `xyz`", "4:13: unknown field `abc`"], errors);
+    }
+}
@@ -0,0 +1,34 @@
+//! Random lists of boarding passes
+use crate::Day5;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day5 {
+    // a seat on each side of mine
+    const MIN_SIZE: usize = 2;
+    // seats in the plane, but the first and the last ones
+    const MAX_SIZE: usize = 1022;
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // consecutive seats, but mine, which is neither the first nor the last
+        let cnt = size.clamp(Self::MIN_SIZE, Self::MAX_SIZE) + 1;
+        let first = rng.range(0..1024 - cnt);
+        let mine = first + rng.range(1..cnt - 1);
+        let mut seat_ids: Vec<usize> = (first..first + cnt).filter(|&id| id != mine).collect();
+        rng.shuffle(&mut seat_ids);
+
+        let mut list = String::new();
+        for id in seat_ids {
+            for bit in (0..10).rev() {
+                let set = id & (1 << bit) != 0;
+                list.push(match (bit >= 3, set) {
+                    (true, false) => 'F',
+                    (true, true) => 'B',
+                    (false, false) => 'L',
+                    (false, true) => 'R',
+                });
+            }
+            list.push('\n');
+        }
+        list
+    }
+}
@@ -0,0 +1,146 @@
+//! Day 5: Binary Boarding
+//!
+//! Decode the binary space partitioned boarding passes, and find my seat.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, records, Line, ParseError, Solution};
+
+mod gen;
+
+// Decode a boarding pass like "FBFBBFFRLR" into its (row, column),
+// once `parse_bdg_pass` checked it is made of 10 of those ASCII letters
+fn to_bdg_pass(s: &str) -> (u32, u32) {
+    let row = &s[0..7].replace('F', "0").replace('B',"1");
+    let col = &s[7..10].replace('L', "0").replace('R',"1");
+    let row = to_int(row);
+    let col = to_int(col);
+    (row, col)
+}
+
+fn to_int (b: &str) -> u32 {
+    let mut result = 0;
+    for c in b.chars() {
+        result *= 2;
+        if let Some(digit) = c.to_digit(2) {
+            result += digit;
+        }
+    }
+    result
+}
+
+/// Check then decode a line of the list of boarding passes into its (row, column)
+pub fn parse_bdg_pass(line: &Line) -> Result<(u32, u32), ParseError> {
+    let s = line.text.trim();
+    let len = s.chars().count();
+    if len != 10 {
+        return Err(line.error(s, format!("expecting 10 characters, found {}", len)));
+    }
+    for (i, c) in s.char_indices() {
+        let expected = if i < 7 { "FB" } else { "LR" };
+        if !expected.contains(c) {
+            return Err(line.error(&s[i..], format!("unexpected `{}`, expecting one of `{}`", c, expected)));
+        }
+    }
+    Ok(to_bdg_pass(s))
+}
+
+/// Return the sorted seat ids of all the boarding passes
+pub fn seat_ids(contents: &str) -> Result<Vec<u32>, ParseError> {
+    let mut seat_ids:Vec<u32> = Vec::new();
+    for boarding_pass in records(contents) {
+        let (r, c) = parse_bdg_pass(&boarding_pass)?;
+        seat_ids.push(r * 8 + c);
+    }
+    if seat_ids.is_empty() {
+        return Err(ParseError::new(0, 0, "no boarding pass"));
+    }
+    seat_ids.sort_unstable();
+    Ok(seat_ids)
+}
+
+/// Solution of day 5
+pub struct Day5;
+
+impl Solution for Day5 {
+    type Input = Vec<u32>;
+    type Answer1 = u32;
+    type Answer2 = u32;
+
+    fn parse(input: &str) -> Result<Vec<u32>, ParseError> {
+        seat_ids(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day5, _, _>(input, parse_bdg_pass)
+    }
+
+    fn part1(seat_ids: &Vec<u32>) -> u32 {
+        *seat_ids.last().unwrap()
+    }
+
+    fn part2(seat_ids: &Vec<u32>) -> u32 {
+        let mut id_prev = *seat_ids.first().unwrap();
+
+        for id in seat_ids {
+            if *id > id_prev + 1 {
+                // found it! it's id_prev + 1
+                break;
+            }
+            id_prev = *id;
+        }
+        id_prev + 1
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn bdg_pass() {
+        assert_eq!(to_bdg_pass("BFFFBBFRRR"), (70, 7));
+        assert_eq!(to_bdg_pass("FFFBBBFRRR"), (14, 7));
+        assert_eq!(to_bdg_pass("BBFFBBFRLL"), (102, 4));
+    }
+
+    #[test]
+    fn malformed_passes() {
+        let errors: Vec<String> = Day5::check("BFFFBBF\nBFFFBBFRRé\nBFFFBBFRLLé\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["1:1: expecting 10 characters, found 7", "2:10: unexpected `é`, expecting one of `LR`", "3:1: expecting 10 characters, found 11"], errors);
+    }
+
+    #[test]
+    fn blank_lines() {
+        assert_eq!(Ok(vec![119, 567]), seat_ids("\nBFFFBBFRRR\n\nFFFBBBFRRR\n\n"));
+    }
+
+    fn encode(seat_id: u32) -> String {
+        (0..10).rev().map(|bit| {
+            let set = seat_id & (1 << bit) != 0;
+            match (bit >= 3, set) {
+                (true, false) => 'F',
+                (true, true) => 'B',
+                (false, false) => 'L',
+                (false, true) => 'R',
+            }
+        }).collect()
+    }
+
+    proptest! {
+        #[test]
+        fn bdg_pass_round_trips(seat_id in 0u32..1024) {
+            let line = encode(seat_id);
+            let (row, col) = parse_bdg_pass(&Line { no: 1, text: &line }).unwrap();
+            prop_assert_eq!(seat_id, row * 8 + col);
+        }
+
+        #[test]
+        fn any_bdg_pass_is_checked(text in "[FBLRX ]{0,12}") {
+            let checked = parse_bdg_pass(&Line { no: 1, text: &text });
+            let expected = text.trim().len() == 10 && text.trim().chars().enumerate()
+                .all(|(i, c)| if i < 7 { c == 'F' || c == 'B' } else { c == 'L' || c == 'R' });
+            prop_assert_eq!(expected, checked.is_ok());
+        }
+    }
+}
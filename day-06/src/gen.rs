@@ -0,0 +1,26 @@
+//! Random answers of groups of passengers
+use crate::Day6;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day6 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut groups = Vec::new();
+        for _ in 0..size {
+            // the questions most people answer "yes" to in this group
+            let popular: Vec<char> = ('a'..='z').filter(|_| rng.one_in(3)).collect();
+            let mut group = String::new();
+            for _ in 0..rng.range(1..6) {
+                let mut answers: String = ('a'..='z')
+                    .filter(|q| if popular.contains(q) { !rng.one_in(5) } else { rng.one_in(8) })
+                    .collect();
+                if answers.is_empty() {
+                    answers.push(*rng.choose(&['a', 'b', 'c']));
+                }
+                group += &answers;
+                group.push('\n');
+            }
+            groups.push(group);
+        }
+        groups.join("\n")
+    }
+}
@@ -0,0 +1,98 @@
+//! Day 6: Custom Customs
+//!
+//! Count the questions answered "yes" by anyone, then by everyone, in each group.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, groups, lines, Line, ParseError, Solution};
+
+mod gen;
+
+/// Sum, over all groups, of the nb of questions to which anyone answered "yes"
+pub fn sum_part1(groups: &[String]) -> usize {
+    let mut result = 0;
+    for this_group in groups {
+        result += cnt_all_answers_in(this_group);
+    }
+    result
+}
+
+fn cnt_all_answers_in(group: &str) -> usize {
+    let mut result = Vec::new();
+    for each_one in group.lines(){
+        for answers in each_one.chars() {
+            if result.contains(&answers) {
+                continue;
+            }
+            result.push(answers);
+        }
+    }
+    result.len()
+}
+
+/// Sum, over all groups, of the nb of questions to which everyone answered "yes"
+pub fn sum_part2(groups: &[String]) -> usize {
+    let mut result = 0;
+    for this_group in groups {
+        result += cnt_common_answer_in(this_group);
+    }
+    result
+}
+
+fn cnt_common_answer_in(group: &str) -> usize {
+    let mut common_answers = "abcdefghijklmnopqrstuvwxyz".to_string();
+    for each_one_answer in group.split_whitespace() {
+        common_answers = strip_characters(&common_answers, each_one_answer);
+    }
+    common_answers.len()
+}
+
+fn strip_characters(original : &str, to_keep : &str) -> String {
+    original.chars().filter(|&c| to_keep.contains(c)).collect()
+}
+
+/// Split the answers in groups, one line per person. Groups are separated by an empty line.
+pub fn split_by_empty_line(input: &str) -> Vec<String> {
+    groups(input)
+        .iter()
+        .map(|group| group.iter().map(|person| person.text.to_string() + "\n").collect())
+        .collect()
+}
+
+// The answers of a person: questions are identified by the letters a to z
+fn check_answers(line: &Line) -> Result<(), ParseError> {
+    match line.text.find(|c: char| !c.is_ascii_lowercase()) {
+        Some(pos) => {
+            let answers = &line.text[pos..];
+            Err(line.error(answers, format!("unexpected answer `{}`", answers.chars().next().unwrap())))
+        }
+        None => Ok(()),
+    }
+}
+
+/// Solution of day 6
+pub struct Day6;
+
+impl Solution for Day6 {
+    type Input = Vec<String>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<String>, ParseError> {
+        for line in lines(input) {
+            check_answers(&line)?;
+        }
+        Ok(split_by_empty_line(input))
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day6, _, _>(input, check_answers)
+    }
+
+    fn part1(groups: &Vec<String>) -> usize {
+        sum_part1(groups)
+    }
+
+    fn part2(groups: &Vec<String>) -> usize {
+        sum_part2(groups)
+    }
+}
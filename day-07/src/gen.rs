@@ -0,0 +1,79 @@
+//! Random bag rules, the bags containing each other making a DAG
+use crate::Day7;
+use aoc_common::{Generator, Rng};
+
+const ADJECTIVES: &[&str] = &[
+    "bright", "clear", "dark", "dim", "dotted", "drab", "dull", "faded", "light", "mirrored",
+    "muted", "pale", "plaid", "posh", "shiny", "striped", "vibrant", "wavy",
+];
+const COLORS: &[&str] = &[
+    "aqua", "beige", "black", "blue", "bronze", "brown", "chartreuse", "coral", "crimson",
+    "cyan", "fuchsia", "gold", "gray", "green", "indigo", "lavender", "lime", "magenta",
+    "maroon", "olive", "orange", "plum", "purple", "red", "salmon", "silver", "tan", "teal",
+    "tomato", "turquoise", "violet", "white", "yellow",
+];
+// A bag only contains bags of the next layers, which keeps the rules acyclic and
+// the nb of bags inside a bag reasonable. The shiny gold bag is in one of the last layers.
+const LAYERS: usize = 14;
+const GOLD_LAYER: usize = 10;
+
+fn name(i: usize) -> String {
+    let (adjective, color) = (ADJECTIVES[i % ADJECTIVES.len()], COLORS[i / ADJECTIVES.len() % COLORS.len()]);
+    match i / (ADJECTIVES.len() * COLORS.len()) {
+        0 => format!("{} {}", adjective, color),
+        n => format!("{} {} {}", adjective, color, n), // once all the colors are taken
+    }
+}
+
+impl Generator for Day7 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut names: Vec<String> = (0..).map(name).filter(|n| n != "shiny gold").take(size.max(3) - 1).collect();
+        names.push("shiny gold".to_string());
+        rng.shuffle(&mut names);
+        let mut layers: Vec<usize> = names.iter().map(|_| rng.range(0..LAYERS)).collect();
+        let gold = names.iter().position(|n| n == "shiny gold").unwrap();
+        let (outer, inner) = ((gold + 1) % names.len(), (gold + 2) % names.len());
+        layers[gold] = GOLD_LAYER;
+        layers[outer] = 0;
+        layers[inner] = LAYERS - 1;
+
+        let mut contents: Vec<Vec<(usize, usize)>> = vec![Vec::new(); names.len()]; // (count, bag)
+        contents[outer].push((rng.range(1..6), gold));
+        contents[gold].push((rng.range(1..6), inner));
+        let mut by_layer = vec![Vec::new(); LAYERS];
+        for (bag, &layer) in layers.iter().enumerate() {
+            by_layer[layer].push(bag);
+        }
+        for bag in 0..names.len() {
+            if layers[bag] == LAYERS - 1 {
+                continue;
+            }
+            for _ in 0..rng.range(0..6) {
+                // mostly the bags of the next layer, so that many bags end up containing shiny gold
+                let layer = if rng.one_in(3) { rng.range(layers[bag] + 1..LAYERS) } else { layers[bag] + 1 };
+                let layer = &by_layer[layer];
+                if layer.is_empty() {
+                    continue;
+                }
+                let b = *rng.choose(layer);
+                if contents[bag].iter().all(|&(_, c)| c != b) {
+                    contents[bag].push((rng.range(1..6), b));
+                }
+            }
+        }
+
+        let mut rules = String::new();
+        for (bag, contents) in contents.iter().enumerate() {
+            let contents: Vec<String> = contents
+                .iter()
+                .map(|&(cnt, b)| format!("{} {} {}", cnt, names[b], if cnt == 1 { "bag" } else { "bags" }))
+                .collect();
+            if contents.is_empty() {
+                rules += &format!("{} bags contain no other bags.\n", names[bag]);
+            } else {
+                rules += &format!("{} bags contain {}.\n", names[bag], contents.join(", "));
+            }
+        }
+        rules
+    }
+}
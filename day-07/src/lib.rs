@@ -0,0 +1,221 @@
+//! Day 7: Handy Haversacks
+//!
+//! Walk the rules telling which bags must contain which other bags.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, lines, Line, ParseError, Solution, Solved};
+use std::collections::{HashMap, HashSet};
+
+mod gen;
+
+/// The bag rules, indexed both ways: contained bags, and container bags
+#[derive(Default)]
+pub struct Rules {
+    the_rules : HashMap<String, Vec<CanContain>>,
+    contained_in : HashMap<String, Vec<String>>
+}
+
+#[derive(Debug, Default)]
+struct CanContain {
+    cnt: usize,
+    name: String
+}
+
+impl Rules {
+    /// Load the rules, one per line. A bag can't end up containing itself.
+    pub fn parse_all_bags(&mut self, str_in: &str) -> Result<(), ParseError> {
+        self.the_rules = HashMap::new();
+        let mut rule_lines = Vec::new(); // in the order of the input
+        for l in lines(str_in) {
+            if l.text.trim().is_empty() { continue; }
+            let (bag_name, bag_props) = self.parse_bag_properties(&l)?;
+            self.the_rules.insert(bag_name, bag_props);
+            rule_lines.push(l);
+        }
+        // counting the bags inside a bag that contains itself would never end
+        let bag_names: Vec<&str> = rule_lines.iter().map(|l| Rules::bag_name(l)).collect();
+        if let Some(cycle) = self.find_cycle(&bag_names) {
+            let l = &rule_lines[bag_names.iter().position(|&name| name == cycle[0]).unwrap()];
+            let bag_name = Rules::bag_name(l);
+            return Err(l.error(bag_name, format!("the {} bags end up containing themselves: {}", bag_name, cycle.join(" > "))));
+        }
+        Ok(())
+    }
+
+    // The name of the bag a rule is about, once the rule is parsed
+    fn bag_name<'a>(l: &Line<'a>) -> &'a str {
+        l.text.split("bags contain").next().unwrap().trim_end()
+    }
+
+    // Bags ending up containing themselves, if any: the first bag of the cycle found,
+    // each bag it contains down to that first bag again. The bags are walked depth first, in
+    // the order of `bag_names`, without recursing: there may be thousands of them in a row.
+    fn find_cycle(&self, bag_names: &[&str]) -> Option<Vec<String>> {
+        let mut done: HashSet<&str> = HashSet::new();
+        for &start in bag_names {
+            if done.contains(start) {
+                continue;
+            }
+            let mut path: Vec<(&str, usize)> = vec![(start, 0)]; // the bags walked, and their next bag inside
+            let mut on_path: HashSet<&str> = HashSet::from([start]);
+            while let Some(&mut (bag, ref mut next)) = path.last_mut() {
+                match self.the_rules.get(bag).and_then(|inside| inside.get(*next)) {
+                    Some(inside) => {
+                        *next += 1;
+                        let inside = inside.name.as_str();
+                        if on_path.contains(inside) {
+                            let from = path.iter().position(|&(walked, _)| walked == inside).unwrap();
+                            let mut cycle: Vec<String> = path[from..].iter().map(|(bag, _)| bag.to_string()).collect();
+                            cycle.push(inside.to_string());
+                            return Some(cycle);
+                        }
+                        if !done.contains(inside) {
+                            path.push((inside, 0));
+                            on_path.insert(inside);
+                        }
+                    }
+                    None => {
+                        done.insert(bag);
+                        on_path.remove(bag);
+                        path.pop();
+                    }
+                }
+            }
+        }
+        None
+    }
+
+    /// List all the bags that can eventually contain `bag_name`.
+    /// Needs `compute_contained_in` to be called first.
+    pub fn list_of_bags_that_can_contain_at_least_one(&self, bag_name: &str) -> Vec<String> {
+        let mut list : Vec<String> = Vec::new();
+        if let Some(parent_bags) = self.contained_in.get(bag_name) {
+            let mut parents_cloned = parent_bags.clone();
+            list.append(&mut parents_cloned);
+            for parent in parent_bags {
+                list.append(&mut self.list_of_bags_that_can_contain_at_least_one(parent).clone());
+            }
+        }
+        list.sort(); // in order to remove duplicates that are not consecutive
+        list.dedup(); // deduplicate !
+        list
+    }
+
+    /// Count the bags inside `bag_name`, including itself, or `None` when the count overflows
+    pub fn nb_of_bags_contained_in(&self, bag_name: &str) -> Option<usize> {
+        let mut cnt : usize = 1;
+        if let Some(bags_inside) = self.the_rules.get(bag_name) {
+            for bag in bags_inside {
+                cnt = cnt.checked_add(bag.cnt.checked_mul(self.nb_of_bags_contained_in(&bag.name)?)?)?;
+            }
+        }
+        Some(cnt)
+    }
+
+    fn parse_bag_properties (&self, line: &Line) -> Result<(String, Vec<CanContain>), ParseError> {
+        // INPUT = vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+        // OUTPUT = ("vibrant plum", BapProperties {
+        //                              bags_in[0] = CanContain {count = 5, name = "faded blue"}
+        //                              bags_in[1] = CanContain {count = 6, name = "dotted black"} }
+
+        let name_props : Vec<&str>= line.text.split("bags contain").collect();
+        if name_props.len() != 2 {
+            return Err(line.error(line.text, "expecting `<color> bags contain <bags>`"));
+        }
+        let bag_name = name_props[0].trim_end();
+        let mut contains = Vec::new();
+        if name_props[1].trim() != "no other bags." {
+            for bag in name_props[1].split(',') {
+                let (cnt, name) = Rules::extract_bag_info(line, bag)?;
+                contains.push(CanContain{cnt, name});
+            }
+        }
+        Ok((String::from(bag_name), contains))
+    }
+
+    /// Build the reverse index: for each bag, the bags that directly contain it
+    pub fn compute_contained_in(&mut self) {
+        // Example with = vibrant plum bags contain 5 faded blue bags, 6 dotted black bags.
+        // INTERNAL UPDATE 1 = add in "faded blue" bag properties that it's contained in "vibrant plum"
+        // INTERNAL UPDATE 2 = add in "dotted black" bag properties that it's contained in "vibrant plum"
+
+        // for each bag in `the_rules`, walk the `CanContain` vec, and insert its name in the appropriate `contained_in` vec
+        for bag_name in self.the_rules.keys() {
+            let contains = self.the_rules.get(bag_name).unwrap();
+            for c in contains {
+                if let Some(target) = self.contained_in.get_mut(&c.name) {
+                    target.push(bag_name.to_string());
+                    continue;
+                }
+                else {
+                    let first_bag = vec![bag_name.to_string()];
+                    self.contained_in.insert(c.name.to_string(), first_bag);
+                }
+            }
+        }
+    }
+
+    fn extract_bag_info(line: &Line, input : &str) -> Result<(usize, String), ParseError> {
+        // split into words "5 faded blue bags" -> "5", "faded", "blue", "bags".
+        let mut name = String::from("");
+
+        let words : Vec<&str> = input.split_whitespace().collect();
+        if words.len() < 3 {
+            return Err(line.error(input.trim_start(), format!("expecting `<count> <color> bags`, found `{}`", input.trim())));
+        }
+        let cnt = line.parse::<usize>(words[0])?; // get the number of bags
+        for word in &words[1..words.len() - 1] { // retrieve the bag name, without the "bag(s)" suffix
+            name = name + word + " ";
+        }
+        name = name.trim_end().to_string();
+        Ok((cnt, name))
+    }
+}
+
+/// Solution of day 7
+pub struct Day7;
+
+impl Solution for Day7 {
+    type Input = Rules;
+    type Answer1 = usize;
+    type Answer2 = Solved<usize>;
+
+    fn parse(input: &str) -> Result<Rules, ParseError> {
+        let mut rules = Rules::default();
+        rules.parse_all_bags(input)?;
+        rules.compute_contained_in();
+        Ok(rules)
+    }
+
+    // every rule, then the bags containing themselves once the rules are fine
+    fn check(input: &str) -> Vec<ParseError> {
+        let rules = Rules::default();
+        check_records::<Day7, _, _>(input, |l| rules.parse_bag_properties(l))
+    }
+
+    fn part1(rules: &Rules) -> usize {
+        rules.list_of_bags_that_can_contain_at_least_one("shiny gold").len()
+    }
+
+    fn part2(rules: &Rules) -> Solved<usize> {
+        match rules.nb_of_bags_contained_in("shiny gold") {
+            Some(cnt) => Solved::Answer(cnt - 1),
+            None => Solved::none("overflow"),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn check_cyclic_rules() {
+        // every rule is fine on its own, but the shiny gold bags would contain themselves
+        let input = "light red bags contain 1 shiny gold bag.\nshiny gold bags contain 1 dark red bag.\ndark red bags contain 2 shiny gold bags.\n";
+        let errors: Vec<String> = Day7::check(input).iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["2:1: the shiny gold bags end up containing themselves: shiny gold > dark red > shiny gold"], errors);
+        let errors: Vec<String> = Day7::check("dark red bags contain 2 dark red bags.\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["1:1: the dark red bags end up containing themselves: dark red > dark red"], errors);
+    }
+}
@@ -0,0 +1,31 @@
+//! Random boot codes, with exactly one corrupted instruction
+use crate::Day8;
+use aoc_common::{Generator, Rng};
+
+// The corrupted instruction is a `jmp` backward, which should have been a `nop`.
+// Before it, no `jmp` goes past it, and every `nop` would jump backward (but not before
+// the start): whatever the other instruction fixed, the program keeps looping.
+// After it, every `jmp` goes forward: fixing the corrupted instruction ends the program.
+impl Generator for Day8 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let len = size.max(2);
+        let corrupted = rng.range(1..len);
+        let mut program = String::new();
+        for add in 0..len {
+            let (op, val) = if add == corrupted {
+                ("jmp", -(rng.range(1..add + 1) as i64))
+            } else if rng.one_in(2) {
+                ("acc", rng.range(0..100) as i64 - 50)
+            } else {
+                let end = if add < corrupted { corrupted } else { len };
+                if rng.one_in(2) {
+                    ("nop", -(rng.range(0..add + 1) as i64))
+                } else {
+                    ("jmp", rng.range(1..end - add + 1) as i64)
+                }
+            };
+            program += &format!("{} {:+}\n", op, val);
+        }
+        program
+    }
+}
@@ -0,0 +1,245 @@
+//! Day 8: Handheld Halting
+//!
+//! A tiny virtual machine, with an accumulator and three instructions.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, records, Frame, Line, NoTrace, ParseError, Solution, Solved, Traced, Tracer};
+use std::fmt;
+
+mod gen;
+
+/// An instruction and its signed argument
+#[derive(Clone, Debug)]
+pub enum OpCode {
+    /// No OPeration, go to the next instruction
+    Nop (i32),
+    /// Add the argument to the accumulator
+    Acc (i32),
+    /// Jump relatively to the current instruction
+    Jmp (i32)
+}
+impl fmt::Display for OpCode {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            OpCode::Nop(x) => write!(f, "nop {:+}", x),
+            OpCode::Acc(x) => write!(f, "acc {:+}", x),
+            OpCode::Jmp(x) => write!(f, "jmp {:+}", x),
+        }
+    }
+}
+
+/// An instruction of the program
+#[derive(Clone, Debug)]
+pub struct Instruction {
+    /// What to execute
+    pub op: OpCode,
+    /// Set once the instruction has been executed
+    pub done: bool
+}
+
+/// The virtual machine, and the program it executes
+#[derive(Clone, Default)]
+pub struct Cpu {
+    /// Accumulator value. Each instruction runs at most once: an i64 can't overflow.
+    pub acc: i64,
+    add: usize, // current Instruction address
+    program: Vec<Instruction>,
+    /// Set when the program was stopped because it was about to loop forever,
+    /// or because it jumped before its first instruction
+    pub aborted: bool
+}
+
+impl Cpu {
+    /// Append the instructions in `input`, one per line, to the program
+    pub fn load (&mut self, input: &str) -> Result<(), ParseError> {
+        for l in records(input) {
+            self.program.push(Cpu::parse_instruction(&l)?);
+        }
+        if self.program.is_empty() {
+            return Err(ParseError::new(0, 0, "the program is empty"));
+        }
+        Ok(())
+    }
+
+    /// Parse a line of the program, like "jmp -4"
+    pub fn parse_instruction(l: &Line) -> Result<Instruction, ParseError> {
+        let mut words = l.text.split_whitespace();
+        let (op_code, val) = match (words.next(), words.next(), words.next()) {
+            (Some(op_code), Some(val), None) => (op_code, val),
+            _ => return Err(l.error(l.text, "expecting `<op> <+/-value>`"))
+        };
+        let val = l.parse::<i32>(val)?;
+        match op_code {
+            "nop" => Ok(Instruction{ op: OpCode::Nop(val), done: false}),
+            "acc" => Ok(Instruction{ op: OpCode::Acc(val), done: false}),
+            "jmp" => Ok(Instruction{ op: OpCode::Jmp(val), done: false}),
+            _ => Err(l.error(op_code, format!("unknown instruction `{}`", op_code)))
+        }
+    }
+
+    /// Execute the program until it ends, or aborts before executing an instruction a second time
+    pub fn run(&mut self) {
+        self.run_traced(&mut NoTrace);
+    }
+
+    /// Execute the program as in `run`, recording the CPU after each instruction
+    pub fn run_traced<T: Tracer>(&mut self, tracer: &mut T) {
+        self.add = 0;
+        let mut instr = self.program.get_mut(self.add).unwrap();
+        while !instr.done {
+            let at = self.add;
+            // None when jumping before the first instruction
+            let next = match instr.op {
+                OpCode::Nop(_x) => Some(self.add + 1),
+                OpCode::Acc(x) => { self.acc += i64::from(x) ; Some(self.add + 1)},
+                OpCode::Jmp(x) => self.add.checked_add_signed(x as isize),
+            };
+            instr.done = true;
+            let (op, acc) = (&instr.op, self.acc);
+            tracer.record(|| Frame::new(format!("{} at {}", op, at)).with("acc", acc).with("next", next));
+
+            self.add = match next {
+                Some(next) => next,
+                None => break,
+            };
+            if self.add >= self.program.len() { // is it end of program?
+                self.aborted = false;
+                return;
+            }
+            instr = self.program.get_mut(self.add).unwrap();
+        }
+        self.aborted = true;
+    }
+
+    /// The instructions of the program
+    pub fn program(&self) -> &[Instruction] {
+        &self.program
+    }
+
+    /// Swap one `jmp` for a `nop` (or the opposite) until the program ends normally, and return
+    /// the address of the instruction swapped. The CPU is left as it is after the successful run.
+    /// `None` if no swap fixes the program: the CPU is then left untouched.
+    pub fn fix(&mut self) -> Option<usize> {
+        // Make modification of the original program (nop by jmp), and try until run() returns
+        for nth in 0..self.program.len() {
+            let mut cpu_2 = Cpu { program: self.program.clone(), ..Default::default() };
+            if let Some(modified_instr) = cpu_2.program.get_mut(nth)
+            {
+                match modified_instr.op
+                {
+                    OpCode::Nop(x) => modified_instr.op = OpCode::Jmp(x),
+                    OpCode::Jmp(x) => modified_instr.op = OpCode::Nop(x),
+                    OpCode::Acc(_x) => { continue; }
+                }
+                cpu_2.run();
+                if !cpu_2.aborted { // program is fixed!
+                    *self = cpu_2;
+                    return Some(nth);
+                }
+            }
+        }
+        None
+    }
+}
+
+/// Solution of day 8
+pub struct Day8;
+
+impl Solution for Day8 {
+    type Input = Cpu;
+    type Answer1 = i64;
+    type Answer2 = Solved<i64>;
+
+    fn parse(input: &str) -> Result<Cpu, ParseError> {
+        let mut cpu = Cpu::default();
+        cpu.load(input)?;
+        Ok(cpu)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day8, _, _>(input, Cpu::parse_instruction)
+    }
+
+    fn part1(cpu: &Cpu) -> i64 {
+        let mut cpu = cpu.clone();
+        cpu.run();
+        cpu.acc
+    }
+
+    fn part2(cpu: &Cpu) -> Solved<i64> {
+        let mut cpu = cpu.clone();
+        match cpu.fix() {
+            Some(_) => Solved::Answer(cpu.acc),
+            None => Solved::none("no single swap of a `jmp` or `nop` makes the program end"),
+        }
+    }
+}
+
+impl Traced for Day8 {
+    fn trace<T: Tracer>(cpu: &Cpu, part: u8, tracer: &mut T) {
+        let mut cpu = cpu.clone();
+        if part == 2 {
+            // trace the run of the fixed program only, not every attempt to fix it
+            match cpu.fix() {
+                Some(nth) => tracer.record(|| Frame::new(format!("fixed by swapping the instruction at {}", nth))),
+                None => {
+                    tracer.record(|| Frame::new("no single swap fixes the program"));
+                    return;
+                }
+            }
+            let program = cpu.program.into_iter().map(|instr| Instruction { done: false, ..instr }).collect();
+            cpu = Cpu { program, ..Default::default() };
+        }
+        cpu.run_traced(tracer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use aoc_common::{Generator, Rng};
+
+    #[test]
+    fn check_every_line() {
+        let errors: Vec<String> = Day8::check("nop +0\nacc x\njmp -1\nmul +2\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["2:5: invalid number `x`: invalid digit found in string", "4:1: unknown instruction `mul`"], errors);
+    }
+
+    #[test]
+    fn large_values() {
+        // jumping before the first instruction aborts, rather than wrapping past the end of the program
+        let mut cpu = Day8::parse("acc +2147483647\nacc +2147483647\njmp -3\n").unwrap();
+        cpu.run();
+        assert!(cpu.aborted);
+        assert_eq!(4294967294, cpu.acc);
+    }
+
+    #[test]
+    fn no_fix() {
+        // every swap still loops forever
+        let cpu = Day8::parse("jmp +0\njmp +0\n").unwrap();
+        assert_eq!(Solved::none("no single swap of a `jmp` or `nop` makes the program end"), Day8::part2(&cpu));
+        let mut unfixed = cpu.clone();
+        assert_eq!(None, unfixed.fix());
+        assert!(!unfixed.aborted);
+    }
+
+    #[test]
+    fn generated_programs_have_one_fix() {
+        for seed in 0..50 {
+            let program = Day8::generate(seed as usize + 2, &mut Rng::new(seed));
+            let cpu = Day8::parse(&program).unwrap();
+            let fixes = (0..cpu.program().len()).filter(|&nth| {
+                let mut fixed = cpu.clone();
+                fixed.program[nth].op = match fixed.program[nth].op {
+                    OpCode::Nop(x) => OpCode::Jmp(x),
+                    OpCode::Jmp(x) => OpCode::Nop(x),
+                    OpCode::Acc(_) => return false,
+                };
+                fixed.run();
+                !fixed.aborted
+            });
+            assert_eq!(1, fixes.count(), "{}", program);
+        }
+    }
+}
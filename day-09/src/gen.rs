@@ -0,0 +1,39 @@
+//! Random XMAS messages
+use crate::Day9;
+use aoc_common::{Generator, Rng};
+
+const PREAMBLE: usize = 25;
+
+impl Generator for Day9 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let len = size.max(PREAMBLE + 3);
+        let mut list: Vec<usize> = (1..=2 * PREAMBLE).collect();
+        rng.shuffle(&mut list);
+        list.truncate(PREAMBLE);
+
+        // Each number is the sum of two of the previous ones, up to the invalid one: the sum
+        // of a contiguous set. Past it, any number goes.
+        let invalid = PREAMBLE + rng.range(0..(len - PREAMBLE - 2).min(500));
+        while list.len() < invalid {
+            let window = &list[list.len() - PREAMBLE..];
+            let a = rng.range(0..PREAMBLE);
+            let b = (a + rng.range(1..PREAMBLE)) % PREAMBLE;
+            list.push(window[a] + window[b]);
+        }
+        loop {
+            let start = rng.range(0..invalid - 1);
+            let end = rng.range(start + 2..invalid.min(start + 17) + 1);
+            let target: usize = list[start..end].iter().sum();
+            let window = &list[invalid - PREAMBLE..];
+            let is_sum = window.iter().enumerate().any(|(i, a)| window[i + 1..].iter().any(|b| a + b == target));
+            if !is_sum {
+                list.push(target);
+                break;
+            }
+        }
+        while list.len() < len {
+            list.push(rng.range(1..1 << 40));
+        }
+        list.iter().map(|nb| format!("{}\n", nb)).collect()
+    }
+}
@@ -0,0 +1,113 @@
+//! Day 9: Encoding Error
+//!
+//! Find the flaw of the XMAS encrypted message.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, lines, read_file, ParseError, Solution};
+
+mod gen;
+
+/// An XMAS message: each number must be the sum of two of the `preamble` numbers before it
+pub struct XmasMsg {
+    preamble: usize,
+    list: Vec<usize>
+}
+
+impl XmasMsg {
+    /// Load the message from a file, one number per line
+    pub fn from_file(f_name: &str, preamble: usize) -> Result<XmasMsg, ParseError> {
+        let str_in = read_file(f_name)?;
+        XmasMsg::from_string(&str_in, preamble).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the message, one number per line
+    pub fn from_string(str_in: &str, preamble: usize) -> Result<XmasMsg, ParseError> {
+        let mut list : Vec<usize> = Vec::new();
+        for l in lines(str_in) {
+            let val = l.text.trim();
+            if val.is_empty() { continue; }
+            list.push(l.parse::<usize>(val)?);
+        }
+        if list.len() <= preamble {
+            return Err(ParseError::new(0, 0, format!("expecting more than {} numbers, found {}", preamble, list.len())));
+        }
+        Ok(XmasMsg {preamble, list})
+    }
+
+    /// Return the first number which is not the sum of two of the previous `preamble` numbers, or 0
+    pub fn find_first_invalid_number(&self) -> usize {
+        // keep a vector of the (n) * (n-1) / 2 acceptable number, n being the preamble value,
+        // as u128: the sum of two numbers can't overflow them
+        let capacity = self.preamble * (self.preamble - 1) / 2;
+        for i in self.preamble..self.list.len() - 1 {
+            let mut acceptable: Vec<u128> = Vec::with_capacity(capacity);
+            for j in (i - self.preamble)..i {
+                for k in (j + 1)..i {
+                    acceptable.push(self.list[j] as u128 + self.list[k] as u128);
+                }
+            }
+            let nb = self.list[i];
+            if acceptable.contains(&(nb as u128)) { // valid
+                continue;
+            }
+            else { // found an invalid one!
+                return nb;
+            }
+        }
+        0
+    }
+
+    /// Find a contiguous set of at least 2 numbers summing up to `target`, and return the sum of its
+    /// smallest and largest numbers, or 0
+    pub fn find_encryption_weakness(&self, target: usize) -> u128 {
+        // a u128 can't overflow, whatever the length of the slice
+        let target = target as u128;
+        for i in 0..self.list.len() {
+            // init the sum with the first element of the new slice
+            let mut contiguous_sum = self.list[i] as u128;
+            for (j, next) in self.list.iter().enumerate().skip(i+1) {
+                // add up the next element in the current candidate slice
+                contiguous_sum += *next as u128;
+                if contiguous_sum > target {
+                    break;
+                }
+                if contiguous_sum == target { // found
+                    let mut set: Vec<usize> = self.list[i..(j+1)].to_vec();
+                    set.sort_unstable();
+                    return *set.first().unwrap() as u128 + *set.last().unwrap() as u128;
+                }
+            }
+        }
+        0
+    }
+}
+
+/// The puzzle uses a preamble of 25 numbers, but the (much shorter) example only uses 5
+pub fn preamble_for(str_in: &str) -> usize {
+    if str_in.split_whitespace().count() > 25 { 25 } else { 5 }
+}
+
+/// Solution of day 9
+pub struct Day9;
+
+impl Solution for Day9 {
+    type Input = XmasMsg;
+    type Answer1 = usize;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<XmasMsg, ParseError> {
+        XmasMsg::from_string(input, preamble_for(input))
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day9, _, _>(input, |line| line.parse::<usize>(line.text.trim()))
+    }
+
+    fn part1(xmas_msg: &XmasMsg) -> usize {
+        xmas_msg.find_first_invalid_number()
+    }
+
+    fn part2(xmas_msg: &XmasMsg) -> u128 {
+        xmas_msg.find_encryption_weakness(xmas_msg.find_first_invalid_number())
+    }
+}
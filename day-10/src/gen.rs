@@ -0,0 +1,22 @@
+//! Random bags of adapters
+use crate::Day10;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day10 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // Runs of up to 4 differences of 1 jolt, separated by differences of 3 jolts, like in
+        // the puzzle
+        let mut adapters = Vec::new();
+        let mut joltage = 0;
+        while adapters.len() < size.max(1) {
+            let run = rng.range(1..5);
+            for _ in 0..run {
+                joltage += 1;
+                adapters.push(joltage);
+            }
+            joltage += 2; // the next run starts with a difference of 3
+        }
+        rng.shuffle(&mut adapters);
+        adapters.iter().map(|a| format!("{}\n", a)).collect()
+    }
+}
@@ -0,0 +1,135 @@
+//! Day 10: Adapter Array
+//!
+//! Chain the joltage adapters from the charging outlet up to the device.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, lines, read_file, Line, ParseError, Solution};
+use aoc_math::big::BigInt;
+use std::collections::{BTreeSet, VecDeque};
+
+mod gen;
+
+/// The joltage ratings of the adapters in my bag
+pub struct Adapters {
+    list: BTreeSet<usize>
+}
+
+impl Adapters {
+    /// Load the adapters from a file, one joltage rating per line
+    pub fn from_file(f_name: &str) -> Result<Adapters, ParseError> {
+        let str_in = read_file(f_name)?;
+        Adapters::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the adapters, one joltage rating per line
+    pub fn from_string(str_in: &str) -> Result<Adapters, ParseError> {
+        let mut list: BTreeSet<usize> = BTreeSet::new();
+        for l in lines(str_in) {
+            let val = l.text.trim();
+            if val.is_empty() { continue; }
+            list.insert(parse_adapter(&l)?);
+        }
+        if list.is_empty() {
+            return Err(ParseError::new(0, 0, "no adapter in my bag"));
+        }
+        Ok(Adapters { list })
+    }
+
+    /// Using all the adapters, the nb of 1-jolt differences multiplied by the nb of 3-jolt differences
+    pub fn nb_of_1jolt_by_3jolts(&self) -> usize {
+        // Accumulate the nb of 1 jolt & 3 jolts diff
+        let mut one_jolt : usize = 1;  // start at 1, charging outlet matters
+        let mut three_jolt : usize = 1;  // start at 1, charging outlet matters
+        // First step
+        let mut iter = self.list.iter();
+        let mut start = iter.next().unwrap();
+        for next in iter {
+            if next - start == 1 {
+                one_jolt += 1;
+            } else if next - start == 3 {
+                three_jolt += 1;
+            }
+            start = next;
+        }
+        one_jolt * three_jolt
+    }
+
+    /// The nb of distinct ways to connect the outlet to the device: it grows exponentially
+    /// with the nb of adapters, past any machine integer
+    pub fn nb_of_possible_arrangements(&self) -> BigInt {
+        // the nb of ways to reach the last 3 adapters, starting with the charging outlet:
+        // the ratings being distinct, only those can be within 3 jolts of the next adapter
+        let mut last_3: VecDeque<(usize, BigInt)> = VecDeque::from(vec![(0, BigInt::from(1))]);
+        for &joltage in &self.list {
+            let ways: BigInt = last_3.iter().filter(|(prev, _)| joltage - prev <= 3).map(|(_, ways)| ways).sum();
+            if last_3.len() == 3 {
+                last_3.pop_front();
+            }
+            last_3.push_back((joltage, ways));
+        }
+        last_3.pop_back().unwrap().1
+    }
+}
+
+// A joltage rating, above the 0 jolts of the charging outlet
+fn parse_adapter(line: &Line) -> Result<usize, ParseError> {
+    let val = line.text.trim();
+    match line.parse::<usize>(val)? {
+        0 => Err(line.error(val, "expecting an adapter rated above the 0 jolts of the outlet")),
+        joltage => Ok(joltage),
+    }
+}
+
+/// Solution of day 10
+pub struct Day10;
+
+impl Solution for Day10 {
+    type Input = Adapters;
+    type Answer1 = usize;
+    type Answer2 = BigInt;
+
+    fn parse(input: &str) -> Result<Adapters, ParseError> {
+        Adapters::from_string(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day10, _, _>(input, parse_adapter)
+    }
+
+    fn part1(adapters: &Adapters) -> usize {
+        adapters.nb_of_1jolt_by_3jolts()
+    }
+
+    fn part2(adapters: &Adapters) -> BigInt {
+        adapters.nb_of_possible_arrangements()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn outlet_rating() {
+        assert_eq!(vec![ParseError::new(2, 1, "expecting an adapter rated above the 0 jolts of the outlet")], Day10::check("1\n0\n"));
+    }
+
+    #[test]
+    fn many_arrangements() {
+        // tribonacci numbers, past u128 with the adapters 1 to 200
+        let input: String = (1..=200).map(|a| format!("{}\n", a)).collect();
+        let adapters = Day10::parse(&input).unwrap();
+        assert!(BigInt::from(u128::MAX) < Day10::part2(&adapters));
+        let input: String = (1..=10).map(|a| format!("{}\n", a)).collect();
+        assert_eq!(BigInt::from(274), Day10::part2(&Day10::parse(&input).unwrap()));
+    }
+
+    #[test]
+    fn high_ratings() {
+        // out of reach of the adapters below: no arrangement, whatever the rating
+        let adapters = Day10::parse("99999999999\n").unwrap();
+        assert_eq!(BigInt::from(0), Day10::part2(&adapters));
+        let adapters = Day10::parse("1\n3\n99999999999\n").unwrap();
+        assert_eq!(BigInt::from(0), Day10::part2(&adapters));
+    }
+}
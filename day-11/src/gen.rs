@@ -0,0 +1,48 @@
+//! Random seat layouts, stabilizing with the rules of both parts
+use crate::{Day11, SeatState, Seats};
+use aoc_common::{Generator, Rng};
+use aoc_grid::{Grid, Xy};
+use std::collections::HashMap;
+
+const WIDTH: usize = 90;
+
+// Apply the rules of `part` until the layout stabilizes, and return None.
+// Some layouts never do, repeating as `Seats::occupy` finds: return the seats flipping in the cycle.
+fn flipping_seats(layout: &Grid<SeatState>, part: i32) -> Option<Vec<Xy>> {
+    let mut seats = Seats { seats: layout.clone(), occupied: 0 };
+    // the layouts before each round, and when they were seen
+    let mut layouts: Vec<Grid<SeatState>> = Vec::new();
+    let mut seen: HashMap<Grid<SeatState>, usize> = HashMap::new();
+    loop {
+        if let Some(&start) = seen.get(&seats.seats) {
+            let cycle = &layouts[start..];
+            let flipping = seats.seats.positions().filter(|&pos| cycle.iter().any(|layout| layout[pos] != seats.seats[pos])).collect();
+            return Some(flipping);
+        }
+        seen.insert(seats.seats.clone(), layouts.len());
+        layouts.push(seats.seats.clone());
+        if seats.run(part) == 0 {
+            return None;
+        }
+    }
+}
+
+impl Generator for Day11 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // `size` rows of empty seats, with a bit of floor
+        let height = size.max(1);
+        let cells = (0..WIDTH * height).map(|_| if rng.one_in(8) { SeatState::Floor } else { SeatState::Empty }).collect();
+        let mut layout = Grid::new(WIDTH, height, cells);
+        // a few of the seats flipping forever are replaced by floor, until none is left
+        while let Some(flipping) = flipping_seats(&layout, 1).or_else(|| flipping_seats(&layout, 2)) {
+            for _ in 0..flipping.len() / 20 + 1 {
+                layout[*rng.choose(&flipping)] = SeatState::Floor;
+            }
+        }
+        layout.render(|state| match state {
+            SeatState::Floor => '.',
+            SeatState::Empty => 'L',
+            SeatState::Occup => '#',
+        })
+    }
+}
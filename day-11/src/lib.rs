@@ -0,0 +1,178 @@
+//! Day 11: Seating System
+//!
+//! People take and leave the seats of the waiting area until it stabilizes.
+#![warn(missing_docs)]
+
+use aoc_common::{read_file, Frame, NoTrace, ParseError, Solution, Solved, Traced, Tracer};
+use aoc_grid::{Grid, Xy, NEIGHBOURS_8};
+use std::collections::HashSet;
+
+mod gen;
+
+/// A position of the seat layout
+#[derive(Clone, Copy, PartialEq, Eq, Hash)]
+pub enum SeatState {
+    /// .
+    Floor,
+    /// L
+    Empty,
+    /// #
+    Occup
+}
+
+// The states of the layout, and how to read them
+const SEAT_STATES: &str = "`.`, `L` or `#`";
+fn seat_state(c: char) -> Option<SeatState> {
+    match c {
+        '.' => Some(SeatState::Floor),
+        'L' => Some(SeatState::Empty),
+        '#' => Some(SeatState::Occup),
+        _ => None
+    }
+}
+
+/// The seat layout of the waiting area
+#[derive(Clone)]
+pub struct Seats {
+    seats: Grid<SeatState>,
+    /// Nb of occupied seats after the latest round
+    pub occupied: i32
+}
+
+impl Seats {
+    /// Apply the rules of `part` (1 or 2) until no seat changes state.
+    /// Return false if the layout repeats instead: the seats never stabilize
+    pub fn occupy(&mut self, part : i32) -> bool {
+        self.occupy_traced(part, &mut NoTrace)
+    }
+
+    /// Apply the rules of `part` (1 or 2) as in `occupy`, recording the layout after each round
+    pub fn occupy_traced<T: Tracer>(&mut self, part: i32, tracer: &mut T) -> bool {
+        tracer.record(|| Frame::new("initial layout").with_map(self.draw()));
+        let mut changes = 1;
+        let mut round = 0;
+        // the layouts before each round
+        let mut seen: HashSet<Grid<SeatState>> = HashSet::new();
+        while changes != 0{
+            if !seen.insert(self.seats.clone()) {
+                tracer.record(|| Frame::new(format!("round {}: the layout repeats, the seats never stabilize", round + 1)));
+                return false;
+            }
+            changes = self.run(part);
+            round += 1;
+            tracer.record(|| Frame::new(format!("round {}: {} seats changed", round, changes))
+                .with("round", round)
+                .with("changes", changes)
+                .with("occupied", self.occupied)
+                .with_map(self.draw()));
+        }
+        true
+    }
+
+    /// The seat layout, as in the puzzle input
+    pub fn draw(&self) -> String {
+        self.seats.render(|state| match state {
+            SeatState::Floor => '.',
+            SeatState::Empty => 'L',
+            SeatState::Occup => '#',
+        })
+    }
+
+    /// Apply the rules of `part` (1 or 2) once to every seat, and return the nb of seats that changed
+    pub fn run(&mut self, part: i32) -> i32 {
+        let mut changes = 0;
+        let mut seats_after : Vec<SeatState> = Vec::with_capacity(self.seats.width() * self.seats.height());
+        self.occupied = 0; // reset!
+        for pos in self.seats.positions() {
+            let mut occupied=0;
+            if part == 1 {occupied = self.compute_occupied(pos, 1);}
+            if part == 2 {occupied = self.compute_occupied(pos, usize::MAX);}
+            let state = self.seats[pos];
+            let new_state;
+            match &state {
+                SeatState::Empty => {
+                    if occupied == 0 {new_state = SeatState::Occup; changes += 1; self.occupied += 1; }
+                    else {new_state = state;}
+                },
+                SeatState::Occup => {
+                    let mut threshold = 4;
+                    if part == 2 {threshold = 5;}
+                    if occupied >= threshold {new_state = SeatState::Empty; changes += 1}
+                    else {new_state = state;  self.occupied += 1;}
+                },
+                SeatState::Floor => new_state = state,
+            }
+            seats_after.push(new_state);
+        }
+        self.seats = Grid::new(self.seats.width(), self.seats.height(), seats_after);
+        changes
+    }
+
+    fn compute_occupied(&self, pos: Xy, iter_max: usize) -> i32 {
+        // look in the 8 directions, up to `iter_max` places away, for the first seat
+        let mut cnt = 0;
+        for dir in NEIGHBOURS_8.iter() {
+            let first_seat = self.seats.cast(pos, *dir)
+                .take(iter_max)
+                .map(|(_, state)| *state)
+                .find(|state| *state != SeatState::Floor);
+            if first_seat == Some(SeatState::Occup) {
+                cnt += 1;
+            }
+        }
+        cnt
+    }
+
+    /// Load the seat layout from a file
+    pub fn from_file(f_name:&str) -> Result<Seats, ParseError> {
+        let str_in = read_file(f_name)?;
+        Seats::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the seat layout
+    pub fn from_string(str_in: &str) -> Result<Seats, ParseError> {
+        let seats = Grid::parse(str_in, SEAT_STATES, seat_state)?;
+        Ok(Seats { seats, occupied : 0 })
+    }
+}
+
+// The nb of occupied seats once the layout stabilizes with the rules of `part`
+fn occupied(seats: &Seats, part: i32) -> Solved<i32> {
+    let mut seats = seats.clone();
+    if seats.occupy(part) {
+        Solved::Answer(seats.occupied)
+    } else {
+        Solved::none("the seats never stabilize")
+    }
+}
+
+/// Solution of day 11
+pub struct Day11;
+
+impl Solution for Day11 {
+    type Input = Seats;
+    type Answer1 = Solved<i32>;
+    type Answer2 = Solved<i32>;
+
+    fn parse(input: &str) -> Result<Seats, ParseError> {
+        Seats::from_string(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        Grid::check(input, SEAT_STATES, seat_state)
+    }
+
+    fn part1(seats: &Seats) -> Solved<i32> {
+        occupied(seats, 1)
+    }
+
+    fn part2(seats: &Seats) -> Solved<i32> {
+        occupied(seats, 2)
+    }
+}
+
+impl Traced for Day11 {
+    fn trace<T: Tracer>(seats: &Seats, part: u8, tracer: &mut T) {
+        seats.clone().occupy_traced(i32::from(part), tracer);
+    }
+}
@@ -0,0 +1,19 @@
+//! Random navigation instructions
+use crate::Day12;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day12 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut instructions = String::new();
+        for _ in 0..size {
+            let instr = match rng.range(0..7) {
+                0..=3 => format!("{}{}", rng.choose(&['N', 'E', 'S', 'W']), rng.range(1..6)),
+                4 | 5 => format!("F{}", rng.range(1..101)),
+                _ => format!("{}{}", rng.choose(&['L', 'R']), rng.choose(&[90, 180, 270])),
+            };
+            instructions += &instr;
+            instructions.push('\n');
+        }
+        instructions
+    }
+}
@@ -0,0 +1,199 @@
+//! Day 12: Rain Risk
+//!
+//! Navigate the ferry following the instructions, moving either the ship or its waypoint.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, read_file, records, Frame, Line, NoTrace, ParseError, Solution, Traced, Tracer};
+
+mod gen;
+
+/// The ferry, its waypoint, and the navigation instructions.
+/// The coordinates are i128: with i32 values, it takes billions of instructions to overflow them.
+#[derive(Clone)]
+pub struct Ship {
+    position : (i128, i128),
+    direction: (i128, i128),
+    way_point: (i128, i128),
+    instructions: Vec<Instr>
+}
+#[derive(Clone)]
+enum Action {North, East, South, West, Left, Right, Forward}
+impl Action {
+    fn letter(&self) -> char {
+        match self {
+            Action::North => 'N',
+            Action::East => 'E',
+            Action::South => 'S',
+            Action::West => 'W',
+            Action::Left => 'L',
+            Action::Right => 'R',
+            Action::Forward => 'F',
+        }
+    }
+}
+#[derive(Clone)]
+struct Instr {
+    action: Action,
+    val: i32,
+}
+impl Ship {
+    /// Follow all the instructions: in part 1 they move the ship, in part 2 they move the waypoint
+    pub fn follow_instructions(&mut self, part: i32) {
+        self.follow_instructions_traced(part, &mut NoTrace);
+    }
+
+    /// Follow all the instructions as in `follow_instructions`, recording the ship and its waypoint after each one
+    pub fn follow_instructions_traced<T: Tracer>(&mut self, part: i32, tracer: &mut T) {
+        for instr in &self.instructions {
+            let val = i128::from(instr.val);
+            match instr.action {
+                Action::North => {
+                    if part == 2 { self.way_point.1 += val; }
+                    else { self.position.1 += val; }
+                },
+                Action::East  => {
+                    if part == 2 { self.way_point.0 += val; }
+                    else { self.position.0 += val; }
+                },
+                Action::South => {
+                    if part == 2 { self.way_point.1 -= val; }
+                    else {self.position.1 -= val;}
+                },
+                Action::West  => {
+                    if part == 2 { self.way_point.0 -= val; }
+                    else { self.position.0 -= val;}
+                },
+                Action::Left => {
+                    if part == 2 { self.way_point = Ship::turn(self.way_point, instr.val);}
+                    else {self.direction = Ship::turn(self.direction, instr.val);}
+                },
+                Action::Right => {
+                    if part == 2 {
+                        self.way_point = Ship::turn(self.way_point, -instr.val);
+                    }
+                    else {self.direction = Ship::turn(self.direction, -instr.val); }
+                },
+                Action::Forward => {
+                    if part == 2 {
+                        self.position.0 += self.way_point.0 * val;
+                        self.position.1 += self.way_point.1 * val;
+                    }
+                    else {
+                        self.position.0 += self.direction.0 * val;
+                        self.position.1 += self.direction.1 * val;
+                    }
+                }
+            }
+            tracer.record(|| {
+                let frame = Frame::new(format!("{}{}", instr.action.letter(), instr.val)).with("position", self.position);
+                if part == 2 { frame.with("waypoint", self.way_point) } else { frame.with("direction", self.direction) }
+            });
+        }
+    }
+
+    // Turn counterclockwise by `angle` degrees, a multiple of 90
+    fn turn(direction: (i128, i128), angle: i32) -> (i128, i128) {
+        match (angle / 90).rem_euclid(4) {
+            0 => direction,
+            1 => (-direction.1, direction.0),
+            2 => (-direction.0, -direction.1),
+            _ => (direction.1, -direction.0),
+        }
+    }
+
+    /// Manhattan distance between the ship and its starting position
+    pub fn manhattan_distance(&self) -> i128 {
+        self.position.0.abs() + self.position.1.abs()
+    }
+
+    /// Put the ship and its waypoint back at their starting positions
+    pub fn reset(&mut self)
+    {
+        self.direction = (1, 0);
+        self.position = (0, 0);
+        self.way_point = (10, 1);
+    }
+
+    /// Load the navigation instructions from a file, like "F10", one per line
+    pub fn from_file(f_name: &str) -> Result<Ship, ParseError> {
+        let str_in = read_file(f_name)?;
+        Ship::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the navigation instructions, like "F10", one per line
+    pub fn from_string(str_in: &str) -> Result<Ship, ParseError> {
+        let mut instructions: Vec<Instr> = Vec::new();
+        for line in records(str_in) {
+            instructions.push(Ship::parse_instr(&line)?);
+        }
+        Ok(Ship { position: (0, 0), direction: (1, 0), way_point: (10, 1), instructions })
+    }
+
+    fn parse_instr(line: &Line) -> Result<Instr, ParseError> {
+        let l = line.text.trim();
+        let split = l.chars().next().unwrap().len_utf8();
+        let (char_action, value) = l.split_at(split);
+        let value = line.parse::<i32>(value)?;
+        match char_action {
+            "N" => Ok(Instr { action: Action::North, val: value }),
+            "E" => Ok(Instr { action: Action::East, val: value }),
+            "S" => Ok(Instr { action: Action::South, val: value }),
+            "W" => Ok(Instr { action: Action::West, val: value }),
+            "L" | "R" if value % 90 != 0 => {
+                Err(line.error(&l[split..], format!("expecting an angle multiple of 90, found {}", value)))
+            }
+            "L" => Ok(Instr { action: Action::Left, val: value }),
+            "R" => Ok(Instr { action: Action::Right, val: value }),
+            "F" => Ok(Instr { action: Action::Forward, val: value }),
+            _ => Err(line.error(char_action, format!("unknown action `{}`", char_action)))
+        }
+    }
+}
+
+/// Solution of day 12
+pub struct Day12;
+
+impl Solution for Day12 {
+    type Input = Ship;
+    type Answer1 = i128;
+    type Answer2 = i128;
+
+    fn parse(input: &str) -> Result<Ship, ParseError> {
+        Ship::from_string(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day12, _, _>(input, Ship::parse_instr)
+    }
+
+    fn part1(ship: &Ship) -> i128 {
+        let mut ship = ship.clone();
+        ship.follow_instructions(1);
+        ship.manhattan_distance()
+    }
+
+    fn part2(ship: &Ship) -> i128 {
+        let mut ship = ship.clone();
+        ship.follow_instructions(2);
+        ship.manhattan_distance()
+    }
+}
+
+impl Traced for Day12 {
+    fn trace<T: Tracer>(ship: &Ship, part: u8, tracer: &mut T) {
+        ship.clone().follow_instructions_traced(i32::from(part), tracer);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn turns() {
+        // the example, turning right by turning left by 270
+        let ship = Ship::from_string("F10\nN3\nF7\nL270\nF11\n").unwrap();
+        assert_eq!((25, 286), (Day12::part1(&ship), Day12::part2(&ship)));
+        assert_eq!(vec![ParseError::new(2, 2, "expecting an angle multiple of 90, found 91")], Day12::check("F10\nR91\nL45\n")[..1]);
+    }
+}
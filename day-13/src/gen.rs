@@ -0,0 +1,20 @@
+//! Random shuttle notes
+use crate::Day13;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day13 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // `size` slots in the schedule, most of them `x`. The bus ids are distinct primes,
+        // so that the buses can always depart one after the other.
+        let mut primes: Vec<usize> = (11..1000).filter(|n| (2..*n).take_while(|d| d * d <= *n).all(|d| n % d != 0)).collect();
+        rng.shuffle(&mut primes);
+        let slots = size.max(1);
+        let mut schedule = vec!["x".to_string(); slots];
+        for (i, prime) in primes.into_iter().take(slots).enumerate() {
+            // the first slot always has a bus
+            let slot = if i == 0 { 0 } else { rng.range(1..slots) };
+            schedule[slot] = prime.to_string();
+        }
+        format!("{}\n{}\n", rng.range(1_000..10_000_000), schedule.join(","))
+    }
+}
@@ -0,0 +1,183 @@
+//! Day 13: Shuttle Search
+//!
+//! Find the bus to take, then the timestamp at which the buses depart one after the other.
+#![warn(missing_docs)]
+
+use aoc_common::{lines, read_file, Line, ParseError, Solution, Solved};
+use aoc_math::big::{self, BigInt};
+use aoc_math::CrtError;
+
+mod gen;
+
+/// The bus schedule, and the earliest time I can depart
+pub struct Shuttles {
+    buses: Vec<usize>,
+    offsets: Vec<usize>, // position of each bus in the schedule
+    departure_time: usize
+}
+
+impl Shuttles {
+    /// Return (bus id, time to wait) of the first bus I can take
+    pub fn find_earliest(&self) -> (usize, usize) {
+        let mut earliest : (usize, usize) = (0, usize::MAX);
+        for id in &self.buses {
+            // +------+-------+ <- bus id = interval_length
+            //             ^    <- departure_time
+            //        <--->     <- departure_time % id = miss_delay
+            //              <-> <- interval_length - miss_delay = time_to_wait
+            // (no wait at all when the bus leaves right at departure_time)
+            let time_to_wait = (id - (self.departure_time % id)) % id;
+            if time_to_wait < earliest.1 {
+                earliest = (*id, time_to_wait);
+            }
+        }
+        earliest
+    }
+
+    /// Return the earliest timestamp such that each bus departs at its offset in the schedule.
+    /// Fails when the buses can never depart in this order, which may happen when their ids aren't coprime.
+    pub fn find_earliest_timestamp(&self) -> Result<BigInt, CrtError> {
+        // the bus `id` at `offset` departs at timestamp + offset: timestamp ≡ -offset (mod id)
+        let congruences: Vec<(BigInt, BigInt)> = self.buses.iter().zip(&self.offsets)
+            .map(|(id, offset)| (-BigInt::from(*offset), BigInt::from(*id)))
+            .collect();
+        big::crt(&congruences).map(|(timestamp, _)| timestamp)
+    }
+
+    /// Load the notes from a file: my earliest departure time, then the comma-separated bus ids (or `x`)
+    pub fn from_file(f_name: &str) -> Result<Shuttles, ParseError> {
+        let str_in = read_file(f_name)?;
+        Shuttles::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the notes: my earliest departure time, then the comma-separated bus ids (or `x`)
+    pub fn from_string(str_in: &str) -> Result<Shuttles, ParseError> {
+        let mut buses: Vec<usize> = Vec::new();
+        let mut offsets: Vec<usize> = Vec::new();
+        let (first_line, second_line) = Shuttles::notes(str_in)?;
+        let depart_time = first_line.parse::<usize>(first_line.text.trim())?;
+        for (offset, id) in second_line.text.trim().split(',').enumerate() {
+            if let Some(bus_id) = Shuttles::parse_bus(&second_line, id)? {
+                buses.push(bus_id);
+                offsets.push(offset);
+            }
+        }
+        // whether the buses can depart in this order is up to part 2: part 1 doesn't care
+        Ok(Shuttles { buses, offsets, departure_time: depart_time })
+    }
+
+    // The two lines of the notes
+    fn notes(str_in: &str) -> Result<(Line<'_>, Line<'_>), ParseError> {
+        let mut notes = lines(str_in).filter(|l| !l.text.trim().is_empty());
+        match (notes.next(), notes.next()) {
+            (Some(first_line), Some(second_line)) => Ok((first_line, second_line)),
+            _ => Err(ParseError::new(0, 0, "expecting the departure time, then the bus ids"))
+        }
+    }
+
+    // A bus id of the schedule, or None when out of service (`x`)
+    fn parse_bus(line: &Line, id: &str) -> Result<Option<usize>, ParseError> {
+        if id == "x" {
+            return Ok(None);
+        }
+        match line.parse::<usize>(id)? {
+            0 => Err(line.error(id, "a bus id can't be 0")),
+            bus_id => Ok(Some(bus_id)),
+        }
+    }
+}
+
+/// Solution of day 13
+pub struct Day13;
+
+impl Solution for Day13 {
+    type Input = Shuttles;
+    type Answer1 = u128;
+    type Answer2 = Solved<BigInt>;
+
+    fn parse(input: &str) -> Result<Shuttles, ParseError> {
+        Shuttles::from_string(input)
+    }
+
+    // the departure time, and every bus id
+    fn check(input: &str) -> Vec<ParseError> {
+        let (first_line, second_line) = match Shuttles::notes(input) {
+            Ok(notes) => notes,
+            Err(e) => return vec![e],
+        };
+        let depart_time = first_line.parse::<usize>(first_line.text.trim()).err();
+        let buses = second_line.text.trim().split(',').filter_map(|id| Shuttles::parse_bus(&second_line, id).err());
+        depart_time.into_iter().chain(buses).collect()
+    }
+
+    fn part1(shuttles: &Shuttles) -> u128 {
+        // the time to wait is below the bus id: their product fits in a u128
+        let (bus_id, ttw) = shuttles.find_earliest();
+        bus_id as u128 * ttw as u128
+    }
+
+    fn part2(shuttles: &Shuttles) -> Solved<BigInt> {
+        match shuttles.find_earliest_timestamp() {
+            Ok(timestamp) => Solved::Answer(timestamp),
+            Err(CrtError::Incompatible(i)) => {
+                Solved::none(format!("bus {} can never depart at its offset after the ones before it", shuttles.buses[i]))
+            }
+            Err(e) => Solved::none(e),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+
+    #[test]
+    fn timestamp() {
+        let shuttles = Shuttles::from_string("939\n7,13,x,x,59,x,31,19").unwrap();
+        assert_eq!(BigInt::from(1068781), shuttles.find_earliest_timestamp().unwrap());
+        // 4 and 6 aren't coprime: t ≡ 0 (mod 4) and t ≡ -2 (mod 6)
+        let shuttles = Shuttles::from_string("0\n4,x,6").unwrap();
+        assert_eq!(BigInt::from(4), shuttles.find_earliest_timestamp().unwrap());
+    }
+
+    #[test]
+    fn incompatible_buses() {
+        // the buses can't depart one after the other, but the earliest one is still there
+        let shuttles = Day13::parse("939\n4,6").unwrap();
+        assert_eq!(4, Day13::part1(&shuttles));
+        assert_eq!(Solved::none("bus 6 can never depart at its offset after the ones before it"), Day13::part2(&shuttles));
+    }
+
+    #[test]
+    fn large_ids() {
+        let shuttles = Day13::parse("1\n18446744073709551615").unwrap();
+        assert_eq!(18446744073709551615 * 18446744073709551614, Day13::part1(&shuttles));
+    }
+
+    #[test]
+    fn check_every_id() {
+        let errors: Vec<String> = Day13::check("9x9\n7,0,x,y,13\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["1:1: invalid number `9x9`: invalid digit found in string", "2:3: a bus id can't be 0", "2:7: invalid number `y`: invalid digit found in string"], errors);
+    }
+
+    proptest! {
+        #[test]
+        fn any_notes_are_checked(notes in "[0-9x,\n ]{0,30}") {
+            // an error or some shuttles: never a panic
+            let _ = Shuttles::from_string(&notes);
+        }
+
+        #[test]
+        fn earliest_bus_is_the_first_to_leave(departure in 0usize..10_000, ids in prop::collection::vec(1usize..100, 1..10)) {
+            // not from notes: ids that aren't coprime may not fit the schedule, part 1 doesn't care
+            let shuttles = Shuttles { buses: ids.clone(), offsets: (0..ids.len()).collect(), departure_time: departure };
+            let (id, wait) = shuttles.find_earliest();
+            prop_assert_eq!(0, (departure + wait) % id);
+            for other in ids {
+                // no bus leaves sooner
+                prop_assert!(wait <= (other - departure % other) % other);
+            }
+        }
+    }
+}
@@ -0,0 +1,25 @@
+//! Random initialization programs
+use crate::Day14;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day14 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // a mask, then a few writes, and so on. At most 9 floating bits per mask, like in the puzzle.
+        let mut program = String::new();
+        let mut writes = 0;
+        for _ in 0..size.max(1) {
+            if writes == 0 {
+                let mut mask: Vec<char> = (0..36).map(|_| *rng.choose(&['0', '1'])).collect();
+                for _ in 0..rng.range(0..10) {
+                    mask[rng.range(0..36)] = 'X';
+                }
+                program += &format!("mask = {}\n", mask.iter().collect::<String>());
+                writes = rng.range(1..6);
+            } else {
+                program += &format!("mem[{}] = {}\n", rng.range(0..1 << 16), rng.range(0..1 << 36));
+                writes -= 1;
+            }
+        }
+        program
+    }
+}
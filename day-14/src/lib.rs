@@ -0,0 +1,269 @@
+//! Day 14: Docking Data
+//!
+//! Run the initialization program, applying the bitmask either to the values or to the addresses.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, read_file, records, Line, ParseError, Solution, Solved};
+use std::collections::HashMap;
+
+mod gen;
+
+/// Most addresses part 2 writes to, all the writes together, before giving up
+pub const MAX_FLOATING_WRITES: u64 = 1 << 24;
+
+#[derive(Clone)]
+enum OpCode{
+    Mask,
+    Write
+}
+#[derive(Clone)]
+struct Instruction {
+    op : OpCode,
+    ad: usize,
+    val: usize,
+    mask: String
+}
+/// The initialization program, and the memory it writes to
+#[derive(Clone)]
+pub struct Program {
+    /// The memory: values by address
+    pub mem: HashMap<usize, usize>,
+    or_mask: usize,
+    and_mask: usize,
+    addr_mask: String,
+    instructions: Vec<Instruction>,
+    /// When set, the mask applies to the addresses (part 2) instead of the values (part 1)
+    pub floating_addrs: bool,
+}
+
+impl Program {
+    /// Execute all the instructions, updating the memory
+    pub fn run(&mut self) {
+        // execute all the instructions, updating the memory
+        for instr in &self.instructions {
+            match instr.op {
+                OpCode::Mask => {
+                    // In part 1, the mask is computed once for all the upcoming Write instructions.
+                    // In part 2, with the floating address, we'll need to keep the mask value as a string,
+                    //  to apply it to each address we want to write to
+                    if self.floating_addrs {
+                        self.addr_mask = instr.mask.clone();
+                    }
+                    else {
+                        let masks= Program::make_mask(&instr.mask);
+                        self.or_mask = masks.0;
+                        self.and_mask = masks.1;
+                    }
+                },
+                OpCode::Write => {
+                    let mut addr_to_write: Vec<usize> = Vec::new();
+                    let val;
+                    if self.floating_addrs {
+                        addr_to_write = self.get_addrs_list(instr.ad);
+                        val = instr.val;
+                    }
+                    else {
+                        addr_to_write.push(instr.ad);
+                        val = self.apply_mask(instr.val);
+                    }
+                    for addr in addr_to_write {
+                        self.mem.insert(addr, val); // if memory cell already exist, it will be updated
+                    }
+                }
+            }
+        }
+    }
+
+    fn get_addrs_list(&self, ad : usize) -> Vec<usize> {
+        // For each AND mask in the list
+        //  For each address already in the address list, apply the AND mask and push it to the address list
+        let and_masks_list = Program::make_addrs_masks(&self.addr_mask);
+
+        // initialize the first address with the address in the instruction ORed with the OR mask obtained in replacing 'X' by '1'
+        let or_mask = self.addr_mask.replace("X", "1");
+        let or_mask = usize::from_str_radix(&or_mask, 2).unwrap();
+        let mut addrs_list = vec![ad | or_mask];
+
+        for mask in and_masks_list {
+            let mut new_addrs_list = Vec::new();
+            for addr in &addrs_list {
+                new_addrs_list.push(addr & mask);
+            }
+            addrs_list.append(&mut new_addrs_list);
+        }
+        addrs_list
+    }
+
+    fn make_addrs_masks(str_in: &str) -> Vec<usize> {
+        // For each 'X' in the mask, create an AND mask with a '0' instead of the 'X'
+        //  and '1's everywhere else
+        let mut addrs_list = Vec::new();
+        let mut next_mask = str_in.to_string();
+        loop {
+            if !next_mask.contains("X") {
+                break;
+            }
+            let new_mask = next_mask.replace("0", "1");
+            let new_mask = new_mask.replacen("X", "0", 1);
+            let new_mask = new_mask.replace("X", "1");
+            addrs_list.push(usize::from_str_radix(&new_mask, 2).unwrap());
+            next_mask = next_mask.replacen("X", "1", 1);
+        }
+        addrs_list
+    }
+
+    // Return (or, and) masks
+    fn make_mask(val:&str) -> (usize, usize) {
+        // OR mask is made by replacing X with 0, and keeping 1 as they are
+        // AND mask is made by replacing X with 1, and keeping 0 as they are
+        let or_str = val.replace("X", "0");
+        let and_str = val.replace("X", "1");
+        let or_mask = usize::from_str_radix(&or_str, 2).unwrap();
+        let and_mask = usize::from_str_radix(&and_str, 2).unwrap();
+        (or_mask, and_mask)
+    }
+
+    fn apply_mask(&self, val:usize) -> usize {
+        let result = val | self.or_mask;
+        result & self.and_mask
+    }
+
+    /// Nb of addresses written when the mask applies to the addresses: each write goes to
+    /// 2^n addresses, n being the nb of floating bits of the mask. Saturates at `u64::MAX`.
+    pub fn floating_writes(&self) -> u64 {
+        let mut floating_bits = 0;
+        let mut writes: u64 = 0;
+        for instr in &self.instructions {
+            match instr.op {
+                OpCode::Mask => floating_bits = instr.mask.matches('X').count(),
+                OpCode::Write => writes = writes.saturating_add(1 << floating_bits),
+            }
+        }
+        writes
+    }
+
+    /// Sum of all the values left in memory
+    pub fn mem_sum(&self) -> u128 {
+        // sum up all the values in the memory, in a u128 that 36-bit values can't overflow
+        let mut sum : u128 = 0;
+        for val in self.mem.values() {
+            sum += *val as u128;
+        }
+        sum
+    }
+
+    /// Load the program from a file, one instruction per line
+    pub fn from_file(f_name: &str) -> Result<Program, ParseError> {
+        let str_in = read_file(f_name)?;
+        Program::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the program, one instruction per line
+    pub fn from_string(str_in: &str) -> Result<Program, ParseError> {
+        let mut instructions = Vec::new();
+        for line in records(str_in) {
+            instructions.push(Program::parse_instruction(&line)?);
+        }
+        Ok(        Program {
+            mem: HashMap::new(),
+            or_mask: 0,
+            and_mask: usize::MAX,
+            addr_mask: "0".repeat(36), // until the first mask, the addresses are left as they are
+            instructions,
+            floating_addrs: false
+        })
+    }
+
+    fn parse_instruction(line: &Line) -> Result<Instruction, ParseError> {
+        let (dest, value) = match line.text.find(" = ") {
+            Some(idx) => (&line.text[..idx], &line.text[idx + 3..]),
+            None => return Err(line.error(line.text, "expecting `<dest> = <value>`"))
+        };
+        if dest == "mask" {
+            if value.len() != 36 || !value.chars().all(|c| "01X".contains(c)) {
+                return Err(line.error(value, "expecting a mask of 36 `0`, `1` or `X`"));
+            }
+            Ok(Instruction{
+                op: OpCode::Mask,
+                ad: 0,
+                val: 0,
+                mask: value.to_string()})
+        }
+        else if let Some(ad) = dest.strip_prefix("mem[").and_then(|ad| ad.strip_suffix(']')) {
+            let ad = Program::parse_36_bits(line, ad)?;
+            let val = Program::parse_36_bits(line, value)?;
+            Ok(Instruction{
+                op: OpCode::Write,
+                ad,
+                val,
+                mask: "".to_string()
+            })
+        }
+        else {
+            Err(line.error(dest, format!("unknown destination `{}`", dest)))
+        }
+    }
+
+    // The addresses and values are 36-bit unsigned integers
+    fn parse_36_bits(line: &Line, token: &str) -> Result<usize, ParseError> {
+        let nb = line.parse::<usize>(token)?;
+        if nb >= 1 << 36 {
+            return Err(line.error(token, format!("expecting a 36-bit value, found {}", nb)));
+        }
+        Ok(nb)
+    }
+}
+
+/// Solution of day 14
+pub struct Day14;
+
+impl Solution for Day14 {
+    type Input = Program;
+    type Answer1 = u128;
+    type Answer2 = Solved<u128>;
+
+    fn parse(input: &str) -> Result<Program, ParseError> {
+        Program::from_string(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day14, _, _>(input, Program::parse_instruction)
+    }
+
+    fn part1(prog: &Program) -> u128 {
+        let mut prog = prog.clone();
+        prog.run();
+        prog.mem_sum()
+    }
+
+    // The sum, unless the program writes to too many addresses to fit in memory
+    fn part2(prog: &Program) -> Solved<u128> {
+        let writes = prog.floating_writes();
+        if writes > MAX_FLOATING_WRITES {
+            return Solved::none(format!("the program writes to {} addresses, more than {}", writes, MAX_FLOATING_WRITES));
+        }
+        let mut prog = prog.clone();
+        prog.floating_addrs = true;
+        prog.run();
+        Solved::Answer(prog.mem_sum())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn floating_writes() {
+        let prog = Day14::parse(&format!("mem[8] = 11\nmask = {}\nmem[7] = 101\n", "X".repeat(36))).unwrap();
+        assert_eq!(1 + (1 << 36), prog.floating_writes());
+        assert_eq!(112, Day14::part1(&prog));
+        assert_eq!("none, the program writes to 68719476737 addresses, more than 16777216", Day14::part2(&prog).to_string());
+    }
+
+    #[test]
+    fn values_of_36_bits() {
+        let errors: Vec<String> = Day14::check("mem[68719476736] = 1\nmem[0] = 68719476736\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["1:5: expecting a 36-bit value, found 68719476736", "2:10: expecting a 36-bit value, found 68719476736"], errors);
+    }
+}
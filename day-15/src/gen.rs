@@ -0,0 +1,15 @@
+//! Random starting numbers
+use crate::Day15;
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day15 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // `size` distinct starting numbers
+        let cnt = size.max(1);
+        let mut numbers: Vec<usize> = (0..2 * cnt).collect();
+        rng.shuffle(&mut numbers);
+        numbers.truncate(cnt);
+        let numbers: Vec<String> = numbers.iter().map(|nb| nb.to_string()).collect();
+        format!("{}\n", numbers.join(","))
+    }
+}
@@ -0,0 +1,107 @@
+//! Day 15: Rambunctious Recitation
+//!
+//! Play the elves' memory game.
+#![warn(missing_docs)]
+
+use aoc_common::{lines, Line, ParseError, Solution};
+use std::collections::HashMap;
+
+mod gen;
+
+/// The number spoken at turn `last_turn`, counting from 1, given the starting numbers
+pub fn nth_number(starting_numbers: &[usize], last_turn: usize) -> usize {
+    if last_turn <= starting_numbers.len() {
+        // still a starting number
+        return starting_numbers[last_turn.max(1) - 1];
+    }
+    let mut list_spoken : HashMap<usize, usize> = HashMap::new();
+    let mut turn : usize = 0;
+    let mut speaking: usize = 0;
+    let mut already_spoken= None;
+
+    // init
+    for &starting_nb in starting_numbers {
+        turn += 1;
+        speaking = starting_nb;
+        already_spoken = list_spoken.insert(speaking, turn);
+    }
+
+    while turn < last_turn {
+        turn += 1;
+        match already_spoken {
+            None => speaking = 0,
+            Some(last_turn_it_was_spoken) => speaking = turn - 1 - last_turn_it_was_spoken,
+        }
+        already_spoken = list_spoken.insert(speaking, turn);
+    }
+    speaking
+}
+
+// The line of the starting numbers, the first one that isn't blank
+fn starting_line(input: &str) -> Result<Line<'_>, ParseError> {
+    match lines(input).find(|l| !l.text.trim().is_empty()) {
+        Some(line) => Ok(line),
+        None => Err(ParseError::new(0, 0, "no starting number"))
+    }
+}
+
+/// Solution of day 15
+pub struct Day15;
+
+impl Solution for Day15 {
+    // the starting numbers, like 0, 3 and 6
+    type Input = Vec<usize>;
+    type Answer1 = usize;
+    type Answer2 = usize;
+
+    fn parse(input: &str) -> Result<Vec<usize>, ParseError> {
+        let line = starting_line(input)?;
+        line.text.trim().split(',').map(|nb| line.parse::<usize>(nb)).collect()
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        match starting_line(input) {
+            Ok(line) => line.text.trim().split(',').filter_map(|nb| line.parse::<usize>(nb).err()).collect(),
+            Err(e) => vec![e],
+        }
+    }
+
+    fn part1(starting_numbers: &Vec<usize>) -> usize {
+        nth_number(starting_numbers, 2020)
+    }
+
+    fn part2(starting_numbers: &Vec<usize>) -> usize {
+        nth_number(starting_numbers, 30_000_000)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn examples() {
+        assert_eq!(0, nth_number(&[0, 3, 6], 10));
+        assert_eq!(436, nth_number(&[0, 3, 6], 2020));
+        assert_eq!(1, nth_number(&[1, 3, 2], 2020));
+        assert_eq!(10, nth_number(&[2, 1, 3], 2020));
+        assert_eq!(27, nth_number(&[1, 2, 3], 2020));
+        assert_eq!(78, nth_number(&[2, 3, 1], 2020));
+        assert_eq!(438, nth_number(&[3, 2, 1], 2020));
+        assert_eq!(1836, nth_number(&[3, 1, 2], 2020));
+        assert_eq!(436, Day15::part1(&Day15::parse("\n0,3,6\n").unwrap()));
+    }
+
+    #[test]
+    fn more_starting_numbers_than_turns() {
+        assert_eq!(6, nth_number(&[0, 3, 6, 1, 2], 3));
+        assert_eq!(2, nth_number(&[0, 3, 6, 1, 2], 5));
+        assert_eq!(0, nth_number(&[0, 3, 6, 1, 2], 6));
+    }
+
+    #[test]
+    fn check_every_number() {
+        let errors: Vec<String> = Day15::check("\n0,x,6,-1\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["2:3: invalid number `x`: invalid digit found in string", "2:7: invalid number `-1`: invalid digit found in string"], errors);
+    }
+}
@@ -0,0 +1,71 @@
+//! Random ticket notes, telling a single order of the fields
+use crate::Day16;
+use aoc_common::{Generator, Rng};
+
+const FIELDS: &[&str] = &[
+    "departure location", "departure station", "departure platform", "departure track",
+    "departure date", "departure time", "arrival location", "arrival station",
+    "arrival platform", "arrival track", "class", "duration", "price", "route", "row", "seat",
+    "train", "type", "wagon", "zone",
+];
+const MAX: usize = 974;
+
+// The fields make a chain, each one accepting fewer values than the previous one:
+// field k accepts `min[k]..=hole.0` and `hole.1..=MAX`. The nearby tickets always have
+// a value in `min[k]..min[k + 1]` in the column of field k, that no later field accepts.
+// The last field only fits its own column, the one before only fits its column and the
+// last one's... which gives a single order.
+impl Generator for Day16 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let hole = rng.range(450..550);
+        let hole = (hole, hole + rng.range(10..30));
+        let mut min: Vec<usize> = (0..FIELDS.len()).map(|k| 25 + 20 * k + rng.range(0..10)).collect();
+        min.push(hole.0 + 1);
+        let mut fields: Vec<&str> = FIELDS.to_vec();
+        rng.shuffle(&mut fields);
+        let mut columns: Vec<usize> = (0..FIELDS.len()).collect();
+        rng.shuffle(&mut columns);
+
+        let ticket = |rng: &mut Rng, chain_value: bool| {
+            let mut values = vec![0; FIELDS.len()];
+            for k in 0..FIELDS.len() {
+                values[columns[k]] = if chain_value {
+                    rng.range(min[k]..min[k + 1])
+                } else if rng.one_in(2) {
+                    rng.range(min[k]..hole.0 + 1)
+                } else {
+                    rng.range(hole.1..MAX + 1)
+                };
+            }
+            values
+        };
+        let mut nearby = vec![ticket(rng, true)];
+        while nearby.len() < size.max(1) {
+            let chain_values = rng.one_in(10);
+            let mut values = ticket(rng, chain_values);
+            if rng.one_in(5) {
+                // not valid for any field
+                let invalid = if rng.one_in(2) { rng.range(hole.0 + 1..hole.1) } else { rng.range(MAX + 1..1000) };
+                values[rng.range(0..FIELDS.len())] = invalid;
+            }
+            nearby.push(values);
+        }
+        rng.shuffle(&mut nearby);
+        let mine = ticket(rng, false);
+
+        let mut rules: Vec<String> = (0..FIELDS.len())
+            .map(|k| format!("{}: {}-{} or {}-{}\n", fields[k], min[k], hole.0, hole.1, MAX))
+            .collect();
+        rng.shuffle(&mut rules);
+        let to_line = |values: &Vec<usize>| {
+            let values: Vec<String> = values.iter().map(|v| v.to_string()).collect();
+            values.join(",") + "\n"
+        };
+        format!(
+            "{}\nyour ticket:\n{}\nnearby tickets:\n{}",
+            rules.concat(),
+            to_line(&mine),
+            nearby.iter().map(to_line).collect::<String>()
+        )
+    }
+}
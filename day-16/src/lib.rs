@@ -0,0 +1,277 @@
+//! Day 16: Ticket Translation
+//!
+//! Discard the invalid tickets, then work out which field is which.
+#![warn(missing_docs)]
+
+use aoc_common::{groups, read_file, Line, ParseError, Solution, Solved};
+use aoc_constraint::{assign, AssignError};
+use regex::Regex;
+use std::collections::{BTreeMap, BTreeSet};
+
+mod gen;
+
+const RULE: &str = r"^ (\d+)-(\d+) or (\d+)-(\d+)$";
+// the headers of the sections of tickets, and what follows them
+const YOUR_TICKET: (&str, &str) = ("your ticket:", "my ticket");
+const NEARBY_TICKETS: (&str, &str) = ("nearby tickets:", "the tickets");
+
+/// A field of the tickets, and its two valid ranges
+#[derive(Clone)]
+pub struct FieldRules {
+    name: String,
+    min_1: usize,
+    max_1: usize,
+    min_2: usize,
+    max_2: usize
+}
+impl FieldRules {
+    fn is_valid(&self, field: &usize) -> bool {
+        (self.min_1..=self.max_1).contains(field) ||
+            (self.min_2..=self.max_2).contains(field)
+    }
+}
+
+/// The field rules, my ticket, and the nearby tickets
+#[derive(Clone)]
+pub struct TicketValidator {
+    rules: Vec<FieldRules>,
+    /// The field values of my ticket
+    pub your_ticket: Vec<usize>,
+    /// The field values of each nearby ticket
+    pub nearby_tickets: Vec<Vec<usize>>
+}
+
+impl TicketValidator {
+    /// Return the first value of the ticket not valid for any field, or 0
+    pub fn invalid_field(&self, ticket: &[usize]) -> usize {
+        // matching any of the rules is enough
+        'field_loop: for field in ticket {
+            'rule_loop: for rule in &self.rules {
+                if rule.is_valid(field) { continue 'field_loop;}
+                continue 'rule_loop;
+            }
+            return *field; // invalid field!
+        }
+        0 // valid ticket
+    }
+
+    /// Return the field names, in the order they appear on the tickets.
+    /// Fails when the valid nearby tickets don't tell a single order.
+    pub fn find_order(&self) -> Result<Vec<String>, AssignError<String>> {
+        // 1. Keep only the valid tickets
+        // 2. For each rule, the possible field indexes are those valid on every ticket
+        // 3. Let the solver give a distinct index to each rule
+        // 4. Create a vector containing the fields in order.
+
+        // Step 1. Keep valid tickets only
+        let valid_tickets: Vec<&Vec<usize>> = self.nearby_tickets.iter()
+            .filter(|ticket| self.invalid_field(ticket) == 0)
+            .collect();
+
+        // Step 2. Compute all the possible field index for each rule
+        let mut candidates = BTreeMap::new();
+        for rule in &self.rules {
+            let idx: BTreeSet<usize> = (0..self.rules.len())
+                .filter(|&field_idx| valid_tickets.iter().all(|ticket| rule.is_valid(&ticket[field_idx])))
+                .collect();
+            candidates.insert(rule.name.clone(), idx);
+        }
+
+        // Step 3. Reduce by starting from the rule that has only one possible index, and propagate to other rules
+        let assigned = assign(candidates)?;
+
+        // Step 4. Create a vector containing the fields in order
+        let mut order = vec![String::new(); self.rules.len()];
+        for (name, idx) in assigned {
+            order[idx] = name;
+        }
+        Ok(order)
+    }
+
+    /// Load the notes from a file: the field rules, my ticket and the nearby tickets, separated by empty lines
+    pub fn from_file(f_name: &str) -> Result<TicketValidator, ParseError> {
+        let str_in = read_file(f_name)?;
+        TicketValidator::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the notes: the field rules, my ticket and the nearby tickets, separated by empty lines
+    pub fn from_string(str_in: &str) -> Result<TicketValidator, ParseError> {
+        // split by empty line:
+        // 1. Field rules
+        // 2. Your ticket
+        // 3. Nearby tickets
+        let parts = groups(str_in);
+        if parts.len() != 3 {
+            return Err(ParseError::new(0, 0, format!("expecting 3 sections separated by empty lines, found {}", parts.len())));
+        }
+
+        let re = Regex::new(RULE).unwrap();
+        let mut rules : Vec<FieldRules> = Vec::new();
+        let rules_cnt = parts[0].len();
+        for rule in &parts[0] {
+            rules.push(TicketValidator::parse_rule(rule, &re)?);
+        }
+
+        let your_ticket = match TicketValidator::section(&parts[1], YOUR_TICKET)? {
+            [ticket] => TicketValidator::parse_ticket(ticket, rules_cnt)?,
+            _ => return Err(TicketValidator::header_error(&parts[1][0], YOUR_TICKET)),
+        };
+
+        let mut nearby_tickets : Vec<Vec<usize>> = Vec::new();
+        for l in TicketValidator::section(&parts[2], NEARBY_TICKETS)? {
+            nearby_tickets.push(TicketValidator::parse_ticket(l, rules_cnt)?);
+        }
+
+        Ok(TicketValidator {
+            rules,
+            your_ticket,
+            nearby_tickets
+        })
+    }
+
+    fn parse_rule(rule: &Line, re: &Regex) -> Result<FieldRules, ParseError> {
+        let name_and_rules:Vec<&str> = rule.text.split(':').collect();
+        if name_and_rules.len() != 2 {
+            return Err(rule.error(rule.text, "expecting `<field>: <min>-<max> or <min>-<max>`"));
+        }
+
+        let name = name_and_rules[0].to_string();
+        let cap = match re.captures(name_and_rules[1]) {
+            Some(cap) => cap,
+            None => return Err(rule.error(name_and_rules[1], "expecting ` <min>-<max> or <min>-<max>`"))
+        };
+        let min_1 = rule.parse::<usize>(cap.get(1).unwrap().as_str())?;
+        let max_1 = rule.parse::<usize>(cap.get(2).unwrap().as_str())?;
+        let min_2 = rule.parse::<usize>(cap.get(3).unwrap().as_str())?;
+        let max_2 = rule.parse::<usize>(cap.get(4).unwrap().as_str())?;
+        Ok(FieldRules{name, min_1, max_1, min_2, max_2})
+    }
+
+    // The lines of a section after its header, `header` being the header and what follows it
+    fn section<'a>(part: &'a [Line<'a>], header: (&str, &str)) -> Result<&'a [Line<'a>], ParseError> {
+        if part[0].text != header.0 {
+            return Err(TicketValidator::header_error(&part[0], header));
+        }
+        Ok(&part[1..])
+    }
+
+    fn header_error(line: &Line, header: (&str, &str)) -> ParseError {
+        line.error(line.text, format!("expecting `{}`, then {}", header.0, header.1))
+    }
+
+    fn parse_ticket(line: &Line, fields_cnt: usize) -> Result<Vec<usize>, ParseError> {
+        let mut ticket : Vec<usize> = Vec::new();
+        let fields : Vec<&str> = line.text.split(',').collect();
+        if fields.len() != fields_cnt {
+            return Err(line.error(line.text, format!("expecting {} fields, found {}", fields_cnt, fields.len())));
+        }
+        for field in fields {
+            ticket.push(line.parse::<usize>(field)?);
+        }
+        Ok(ticket)
+    }
+
+    /// Sum of the values of the nearby tickets that are not valid for any field,
+    /// in a u128 that a sum of usize values can't overflow
+    pub fn scanning_error_rate(&self) -> u128 {
+        let mut scanning_error_rate = 0;
+        for nearby_ticket in &self.nearby_tickets {
+            scanning_error_rate += self.invalid_field(nearby_ticket) as u128;
+        }
+        scanning_error_rate
+    }
+
+    /// Multiply together the values of the fields of my ticket whose name starts with "departure".
+    /// The product is `None` when it overflows.
+    pub fn departure_product(&self) -> Result<Option<usize>, AssignError<String>> {
+        let order = self.find_order()?;
+        let mut result = Some(1usize);
+        for (field_idx, ordered_field) in order.iter().enumerate() {
+            if ordered_field.starts_with("departure") {
+                result = result.and_then(|product| product.checked_mul(self.your_ticket[field_idx]));
+            }
+        }
+        Ok(result)
+    }
+}
+
+/// Solution of day 16
+pub struct Day16;
+
+impl Solution for Day16 {
+    type Input = TicketValidator;
+    type Answer1 = u128;
+    type Answer2 = Solved<usize>;
+
+    fn parse(input: &str) -> Result<TicketValidator, ParseError> {
+        TicketValidator::from_string(input)
+    }
+
+    // every rule, header and ticket, once the input has its 3 sections
+    fn check(input: &str) -> Vec<ParseError> {
+        let parts = groups(input);
+        if parts.len() != 3 {
+            return Day16::parse(input).err().into_iter().collect();
+        }
+        let re = Regex::new(RULE).unwrap();
+        let rules_cnt = parts[0].len();
+        let mut errors: Vec<ParseError> = parts[0].iter().filter_map(|rule| TicketValidator::parse_rule(rule, &re).err()).collect();
+        for (part, header) in [(&parts[1], YOUR_TICKET), (&parts[2], NEARBY_TICKETS)] {
+            match TicketValidator::section(part, header) {
+                Ok(tickets) => errors.extend(tickets.iter().filter_map(|l| TicketValidator::parse_ticket(l, rules_cnt).err())),
+                Err(e) => errors.push(e),
+            }
+        }
+        if errors.is_empty() {
+            // every line is fine, but my ticket may not be alone in its section
+            return Day16::parse(input).err().into_iter().collect();
+        }
+        errors
+    }
+
+    fn part1(tickets: &TicketValidator) -> u128 {
+        tickets.scanning_error_rate()
+    }
+
+    // The product, or why the order of the fields can't be worked out
+    fn part2(tickets: &TicketValidator) -> Solved<usize> {
+        match tickets.departure_product() {
+            Ok(Some(product)) => Solved::Answer(product),
+            Ok(None) => Solved::none("overflow"),
+            Err(e) => Solved::none(format!("can't work out the order of the fields: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn order() {
+        let tickets = TicketValidator::from_string(include_str!("../example2.txt")).unwrap();
+        assert_eq!(Ok(vec!["row".to_string(), "class".to_string(), "seat".to_string()]), tickets.find_order());
+    }
+
+    #[test]
+    fn ambiguous_order() {
+        let input = "a: 1-2 or 5-6\nb: 1-2 or 5-6\n\nyour ticket:\n1,5\n\nnearby tickets:\n2,6\n9,1\n";
+        let tickets = Day16::parse(input).unwrap();
+        assert_eq!(9, Day16::part1(&tickets));
+        assert_eq!(Solved::none("can't work out the order of the fields: several values are possible for `a`, `b`"), Day16::part2(&tickets));
+    }
+
+    #[test]
+    fn check_every_ticket() {
+        let input = "a: 1-2 or 5-6\nb: 1-2 or 5-x\n\nyour ticket:\n1,5\n\nnearby tickets:\n2,6\n9\n9,1\n1,y\n";
+        let errors: Vec<String> = Day16::check(input).iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["2:3: expecting ` <min>-<max> or <min>-<max>`", "9:1: expecting 2 fields, found 1", "11:3: invalid number `y`: invalid digit found in string"], errors);
+    }
+
+    #[test]
+    fn departure_product_overflow() {
+        let input = "departure a: 1-2 or 5-9999999999\ndeparture b: 3-4 or 5-9999999999\n\nyour ticket:\n9999999999,9999999999\n\nnearby tickets:\n1,3\n";
+        let tickets = Day16::parse(input).unwrap();
+        assert_eq!(Solved::none("overflow"), Day16::part2(&tickets));
+    }
+}
@@ -0,0 +1,41 @@
+//! Random homework
+use crate::Day18;
+use aoc_common::{Generator, Rng};
+
+// At most 12 digits per line: a line evaluates to less than 10^12 whatever the precedence
+// (each digit d at most multiplies the result by d + 1)
+const DIGITS: usize = 12;
+
+// `<term> <op> <term>...`, a term being a digit or a sub-expression in parentheses,
+// using at most `digits` digits (at least 2)
+fn operations(rng: &mut Rng, digits: &mut usize, depth: usize) -> String {
+    let terms = rng.range(2..5).min(*digits);
+    let mut expr = String::new();
+    for i in 0..terms {
+        if i > 0 {
+            expr += if rng.one_in(2) { " + " } else { " * " };
+        }
+        let available = *digits - (terms - i - 1); // one digit for each of the next terms
+        if depth < 3 && available >= 2 && rng.one_in(3) {
+            let mut left = available;
+            expr += &format!("({})", operations(rng, &mut left, depth + 1));
+            *digits -= available - left;
+        } else {
+            expr += &rng.range(1..10).to_string();
+            *digits -= 1;
+        }
+    }
+    expr
+}
+
+impl Generator for Day18 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut homework = String::new();
+        for _ in 0..size {
+            let mut digits = DIGITS;
+            homework += &operations(rng, &mut digits, 0);
+            homework.push('\n');
+        }
+        homework
+    }
+}
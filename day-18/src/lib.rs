@@ -0,0 +1,342 @@
+//! Day 18: Operation Order
+//!
+//! Evaluate the homework expressions, where `+` and `*` have the same precedence (part 1)
+//! or where `+` is evaluated before `*` (part 2).
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, records, ParseError, Solution, Solved};
+
+mod gen;
+
+/// An operand of an expression
+pub enum Operand {
+    /// In case we have directly a digit
+    Val(usize),
+    /// In case we must evaluate a sub expression to get the value
+    Expr(Box<Expression>),
+}
+
+/// The operator of an operation
+#[derive(PartialEq)]
+pub enum Operator {
+    /// `+`
+    Add,
+    /// `*`
+    Mul,
+}
+
+/// An expression as written: its first operand, then each operator and the operand following it.
+/// The precedence of the operators is only known when evaluating it.
+pub struct Expression {
+    first: Operand,
+    rest: Vec<(Operator, Operand)>,
+}
+
+impl Expression {
+    /// Parse the expression of a line, whatever the precedence of the operators
+    pub fn from_string(str_in: &str) -> Result<Self, ParseError> {
+        let chars: Vec<char> = str_in.chars().collect();
+        let mut char_idx: usize = 0;
+        Expression::parse_expression(&chars, &mut char_idx, None)
+    }
+
+    // An operand, then the operations following it, up to the end of line or, after the '(' at
+    // `open`, up to its ')'
+    fn parse_expression(chars: &[char], char_idx: &mut usize, open: Option<usize>) -> Result<Expression, ParseError> {
+        let first = Expression::parse_operand(chars, char_idx)?;
+        let mut expr = Expression { first, rest: Vec::new() };
+        loop {
+            Expression::skip_spaces(chars, char_idx);
+            match (chars.get(*char_idx), open) {
+                (None, None) => return Ok(expr),
+                (None, Some(open)) => {
+                    let msg = format!("expecting ')' to close the '(' at column {}, found the end of line", open + 1);
+                    return Err(ParseError::new(0, *char_idx + 1, msg));
+                }
+                (Some(')'), None) => return Err(ParseError::new(0, *char_idx + 1, "unmatched ')'")),
+                (Some(')'), Some(_)) => {
+                    *char_idx += 1;
+                    return Ok(expr);
+                }
+                _ => {
+                    let operator = Expression::parse_operator(chars, char_idx)?;
+                    let operand = Expression::parse_operand(chars, char_idx)?;
+                    expr.rest.push((operator, operand));
+                }
+            }
+        }
+    }
+
+    fn parse_operand(chars: &[char], char_idx: &mut usize) -> Result<Operand, ParseError> {
+        Expression::skip_spaces(chars, char_idx);
+        match chars.get(*char_idx) {
+            Some(c) if c.is_ascii_digit() => {
+                *char_idx += 1;
+                Ok(Operand::Val(c.to_digit(10).unwrap() as usize))
+            }
+            Some('(') => {
+                let open = *char_idx;
+                *char_idx += 1;
+                let sub_expression = Expression::parse_expression(chars, char_idx, Some(open))?;
+                Ok(Operand::Expr(Box::new(sub_expression)))
+            }
+            _ => Err(Expression::error(chars, *char_idx, "expecting a digit or '('")),
+        }
+    }
+
+    fn parse_operator(chars: &[char], char_idx: &mut usize) -> Result<Operator, ParseError> {
+        Expression::skip_spaces(chars, char_idx);
+        let operator = match chars.get(*char_idx) {
+            Some('+') => Operator::Add,
+            Some('*') => Operator::Mul,
+            _ => return Err(Expression::error(chars, *char_idx, "expecting operator '+' or '*'")),
+        };
+        *char_idx += 1;
+        Ok(operator)
+    }
+
+    fn error(chars: &[char], char_idx: usize, msg: &str) -> ParseError {
+        match chars.get(char_idx) {
+            Some(c) => ParseError::new(0, char_idx + 1, format!("{}, found '{}'", msg, c)),
+            None => ParseError::new(0, char_idx + 1, format!("{}, found the end of line", msg)),
+        }
+    }
+
+    fn skip_spaces(chars: &[char], char_idx: &mut usize) {
+        while chars.get(*char_idx) == Some(&' ') {
+            *char_idx += 1;
+        }
+    }
+
+    /// Compute the value of the expression, or `None` when it overflows.
+    /// When `add_precedes` is set, `+` is evaluated before `*`, otherwise from left to right.
+    pub fn evaluate(&self, add_precedes: bool) -> Option<usize> {
+        let mut terms = self.rest.iter().map(|(operator, operand)| Some((operator, operand.evaluate(add_precedes)?)));
+        if add_precedes {
+            // a product of sums
+            let mut product = 1usize;
+            let mut sum = self.first.evaluate(add_precedes)?;
+            for term in &mut terms {
+                match term? {
+                    (Operator::Add, value) => sum = sum.checked_add(value)?,
+                    (Operator::Mul, value) => {
+                        product = product.checked_mul(sum)?;
+                        sum = value;
+                    }
+                }
+            }
+            product.checked_mul(sum)
+        } else {
+            terms.try_fold(self.first.evaluate(add_precedes)?, |result, term| match term? {
+                (Operator::Add, value) => result.checked_add(value),
+                (Operator::Mul, value) => result.checked_mul(value),
+            })
+        }
+    }
+}
+
+impl Operand {
+    fn evaluate(&self, add_precedes: bool) -> Option<usize> {
+        match self {
+            Operand::Val(x) => Some(*x),
+            Operand::Expr(x) => x.evaluate(add_precedes),
+        }
+    }
+}
+
+/// Parse the homework: an expression per line
+pub fn parse_homework(str_in: &str) -> Result<Vec<Expression>, ParseError> {
+    records(str_in).map(|line| Expression::from_string(line.text).map_err(|e| e.at_line(line.no))).collect()
+}
+
+/// Sum of the results of every expression of the homework, or `None` when it overflows
+pub fn sum_all(homework: &[Expression], add_precedes: bool) -> Option<usize> {
+    homework.iter().try_fold(0usize, |sum, expr| sum.checked_add(expr.evaluate(add_precedes)?))
+}
+
+// The sum, or why there is none
+fn answer(sum: Option<usize>) -> Solved<usize> {
+    match sum {
+        Some(sum) => Solved::Answer(sum),
+        None => Solved::none("overflow"),
+    }
+}
+
+/// Solution of day 18
+pub struct Day18;
+
+impl Solution for Day18 {
+    type Input = Vec<Expression>;
+    type Answer1 = Solved<usize>;
+    type Answer2 = Solved<usize>;
+
+    fn parse(input: &str) -> Result<Vec<Expression>, ParseError> {
+        parse_homework(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day18, _, _>(input, |l| Expression::from_string(l.text).map_err(|e| e.at_line(l.no)))
+    }
+
+    fn part1(homework: &Vec<Expression>) -> Solved<usize> {
+        answer(sum_all(homework, false))
+    }
+
+    fn part2(homework: &Vec<Expression>) -> Solved<usize> {
+        answer(sum_all(homework, true))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use proptest::prelude::*;
+    use std::iter::Peekable;
+    use std::str::Chars;
+
+    #[test]
+    fn tst_11() {
+        let expr = Expression::from_string("1 + 2 * 3 + 4 * 5 + 6").unwrap();
+        assert_eq!(Some(71), expr.evaluate(false));
+    }
+    #[test]
+    fn tst_12() {
+        let expr = Expression::from_string("1 + (2 * 3) + (4 * (5 + 6))").unwrap();
+        assert_eq!(Some(51), expr.evaluate(false));
+    }
+    #[test]
+    fn tst_13() {
+        let expr = Expression::from_string("2 * 3 + (4 * 5)").unwrap();
+        assert_eq!(Some(26), expr.evaluate(false));
+    }
+    #[test]
+    fn tst_14() {
+        let expr = Expression::from_string("5 + (8 * 3 + 9 + 3 * 4 * 3)").unwrap();
+        assert_eq!(Some(437), expr.evaluate(false));
+    }
+    #[test]
+    fn tst_15() {
+        let expr = Expression::from_string("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))").unwrap();
+        assert_eq!(Some(12240), expr.evaluate(false));
+    }
+    #[test]
+    fn tst_16() {
+        let expr =
+            Expression::from_string("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2").unwrap();
+        assert_eq!(Some(13632), expr.evaluate(false));
+    }
+
+    #[test]
+    fn tst_21() {
+        let expr = Expression::from_string("1 + 2 * 3 + 4 * 5 + 6").unwrap();
+        assert_eq!(Some(231), expr.evaluate(true));
+    }
+    #[test]
+    fn tst_22() {
+        let expr = Expression::from_string("1 + (2 * 3) + (4 * (5 + 6))").unwrap();
+        assert_eq!(Some(51), expr.evaluate(true));
+    }
+    #[test]
+    fn tst_23() {
+        let expr = Expression::from_string("2 * 3 + (4 * 5)").unwrap();
+        assert_eq!(Some(46), expr.evaluate(true));
+    }
+    #[test]
+    fn tst_24() {
+        let expr = Expression::from_string("5 + (8 * 3 + 9 + 3 * 4 * 3)").unwrap();
+        assert_eq!(Some(1445), expr.evaluate(true));
+    }
+    #[test]
+    fn tst_25() {
+        let expr = Expression::from_string("5 * 9 * (7 * 3 * 3 + 9 * 3 + (8 + 6 * 4))").unwrap();
+        assert_eq!(Some(669060), expr.evaluate(true));
+    }
+    #[test]
+    fn tst_26() {
+        let expr = Expression::from_string("((2 + 4 * 9) * (6 + 9 * 8 + 6) + 6) + 2 + 4 * 2").unwrap();
+        assert_eq!(Some(23340), expr.evaluate(true));
+    }
+
+    #[test]
+    fn tst_error() {
+        let err = parse_homework("1 + 2\n3 * (4 + x)\n").err().unwrap();
+        assert_eq!("2:10: expecting a digit or '(', found 'x'", err.to_string());
+    }
+
+    #[test]
+    fn parentheses() {
+        let errors = |homework: &str| -> Vec<String> { Day18::check(homework).iter().map(|e| e.to_string()).collect() };
+        assert_eq!(vec!["1:6: unmatched ')'", "2:6: unmatched ')'"], errors("1 + 2)\n5 + 6) * 7\n"));
+        assert_eq!(vec!["1:13: expecting ')' to close the '(' at column 5, found the end of line"], errors("1 * (2 + (3)"));
+        assert_eq!(vec!["1:4: expecting operator '+' or '*', found '4'"], errors("(3 4)"));
+        // a bracketed or single operand is a whole expression
+        let homework = Day18::parse("(3 * 4)\n7\n((5))\n").unwrap();
+        assert_eq!((Solved::Answer(24), Solved::Answer(24)), (Day18::part1(&homework), Day18::part2(&homework)));
+    }
+
+    // Evaluate the terms up to the next ')' or the end of line, the simple way:
+    // first gather the terms and operators, then reduce them. `None` on overflow.
+    fn reference(chars: &mut Peekable<Chars>, add_precedes: bool) -> Option<usize> {
+        let mut terms = Vec::new();
+        let mut operators = Vec::new();
+        while let Some(c) = chars.next() {
+            match c {
+                ' ' => {}
+                '(' => terms.push(reference(chars, add_precedes)?),
+                ')' => break,
+                '+' | '*' => operators.push(c),
+                _ => terms.push(c.to_digit(10).unwrap() as usize),
+            }
+        }
+        if add_precedes {
+            // a product of sums
+            let mut product = 1usize;
+            let mut sum = terms[0];
+            for (op, term) in operators.iter().zip(&terms[1..]) {
+                if *op == '+' {
+                    sum = sum.checked_add(*term)?;
+                } else {
+                    product = product.checked_mul(sum)?;
+                    sum = *term;
+                }
+            }
+            product.checked_mul(sum)
+        } else {
+            let mut result = terms[0];
+            for (op, term) in operators.iter().zip(&terms[1..]) {
+                result = if *op == '+' { result.checked_add(*term)? } else { result.checked_mul(*term)? };
+            }
+            Some(result)
+        }
+    }
+
+    // `<term> <op> <term>...`, a term being a digit or a sub-expression in parentheses
+    fn operations(term: impl Strategy<Value = String> + Clone) -> impl Strategy<Value = String> {
+        (term.clone(), prop::collection::vec((prop_oneof![Just(" + "), Just(" * ")], term), 1..4))
+            .prop_map(|(first, rest)| rest.into_iter().fold(first, |expr, (op, term)| expr + op + &term))
+    }
+
+    fn expression() -> impl Strategy<Value = String> {
+        let digit = (1u32..10).prop_map(|d| d.to_string());
+        let term = digit.prop_recursive(3, 32, 3, |term| operations(term).prop_map(|expr| format!("({})", expr)));
+        operations(term)
+    }
+
+    proptest! {
+        #[test]
+        fn evaluation_matches_reference(expr in expression(), add_precedes: bool) {
+            // operands are never 0: no intermediate value is greater than the result
+            let expected = reference(&mut expr.chars().peekable(), add_precedes);
+            let evaluated = Expression::from_string(&expr).unwrap().evaluate(add_precedes);
+            prop_assert_eq!(expected, evaluated);
+        }
+
+        #[test]
+        fn any_line_is_checked(text in "[0-9()+* x]{0,20}") {
+            // an error located on the line, or a value: never a panic
+            if let Err(e) = parse_homework(&text) {
+                prop_assert_eq!(1, e.line);
+            }
+        }
+    }
+}
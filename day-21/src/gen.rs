@@ -0,0 +1,60 @@
+//! Random food lists, telling which ingredient contains each allergen
+use crate::Day21;
+use aoc_common::{Generator, Rng};
+
+const ALLERGENS: &[&str] = &["dairy", "eggs", "fish", "nuts", "peanuts", "sesame", "shellfish", "soy", "wheat"];
+const INERTS: usize = 200;
+
+fn food(ingredients: &mut [String], allergens: &[&str], rng: &mut Rng) -> String {
+    rng.shuffle(ingredients);
+    format!("{} (contains {})\n", ingredients.join(" "), allergens.join(", "))
+}
+
+// Each allergen is in a single ingredient. Besides the random foods, two foods list each
+// allergen, sharing no other ingredient than the one containing it: that pins it.
+impl Generator for Day21 {
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        let mut names: Vec<String> = Vec::new();
+        while names.len() < ALLERGENS.len() + INERTS {
+            let name: String = (0..rng.range(4..9)).map(|_| (b'a' + rng.range(0..26) as u8) as char).collect();
+            if !names.contains(&name) {
+                names.push(name);
+            }
+        }
+        let mut inerts = names.split_off(ALLERGENS.len());
+        let dangerous = names; // dangerous[i] contains ALLERGENS[i]
+
+        let mut foods = Vec::new();
+        for _ in 0..size {
+            rng.shuffle(&mut inerts);
+            let mut ingredients: Vec<String> = inerts[..rng.range(5..16)].to_vec();
+            let mut allergens = Vec::new();
+            for (i, allergen) in ALLERGENS.iter().enumerate() {
+                if rng.one_in(4) {
+                    ingredients.push(dangerous[i].clone());
+                    // the allergens are not always listed
+                    if !rng.one_in(3) {
+                        allergens.push(*allergen);
+                    }
+                }
+            }
+            if allergens.is_empty() {
+                let i = rng.range(0..ALLERGENS.len());
+                ingredients.push(dangerous[i].clone());
+                allergens.push(ALLERGENS[i]);
+            }
+            allergens.sort_unstable();
+            foods.push(food(&mut ingredients, &allergens, rng));
+        }
+        for (i, allergen) in ALLERGENS.iter().enumerate() {
+            rng.shuffle(&mut inerts);
+            for pinning in inerts[..20].chunks(10) {
+                let mut ingredients = pinning.to_vec();
+                ingredients.push(dangerous[i].clone());
+                foods.push(food(&mut ingredients, &[allergen], rng));
+            }
+        }
+        rng.shuffle(&mut foods);
+        foods.concat()
+    }
+}
@@ -0,0 +1,175 @@
+//! Day 21: Allergen Assessment
+//!
+//! Work out which ingredient contains which allergen.
+#![warn(missing_docs)]
+
+use aoc_common::{check_records, read_file, records, Line, ParseError, Solution, Solved};
+use aoc_constraint::{assign, AssignError};
+use std::collections::{BTreeMap, BTreeSet, HashMap, HashSet};
+use std::iter::FromIterator;
+
+mod gen;
+
+#[derive(Clone)]
+struct Food {
+    ingredients: Vec<String>,
+    allergens: Vec<String>,
+}
+/// The foods, and the ingredients possibly containing each allergen
+#[derive(Clone)]
+pub struct FoodList {
+    // List of food with ingredients, and possible allergens
+    food: Vec<Food>,
+    // Correspondence between each allergen and container ingredients
+    allergens_table: HashMap<String, HashSet<String>>,
+}
+
+impl FoodList {
+    /// Return the ingredients that can't contain any allergen.
+    /// Also narrows down the ingredients possibly containing each allergen.
+    pub fn inert_ingredients(&mut self) -> HashSet<String> {
+        // Make a list the ingredients that are not in any list of possible container of allergens
+        let mut inert_list: HashSet<String> = HashSet::new();
+
+        // for each food in the list
+        //  for each allergens in this food
+        //    compute the intersection of the new set of ingredients possibly containing this allergen
+        //     with existing set. Replace the ingredients list by the intersected one.
+        let mut all_ingredients: HashSet<String> = HashSet::new();
+        for f in &self.food {
+            for a in &f.allergens {
+                let mut new_set = HashSet::from_iter(f.ingredients.iter().cloned());
+                if let Some(existing_set) = self.allergens_table.get(a) {
+                    new_set = existing_set.intersection(&new_set).cloned().collect();
+                }
+                self.allergens_table.insert(a.clone(), new_set);
+
+                for i in &f.ingredients {
+                    all_ingredients.insert(i.clone());
+                }
+            }
+        }
+        'ingredient: for i in &all_ingredients {
+            for a in &self.allergens_table {
+                if a.1.contains(i) {
+                    continue 'ingredient;
+                }
+            }
+            inert_list.insert(i.clone());
+        }
+        inert_list
+    }
+
+    /// Count the occurrences of the inert ingredients in the foods
+    pub fn count_inert(&self, inert_list: &HashSet<String>) -> usize {
+        let mut inert_cnt = 0;
+        for f in &self.food {
+            for i in &f.ingredients {
+                if inert_list.contains(i) {
+                    inert_cnt += 1;
+                }
+            }
+        }
+        inert_cnt
+    }
+
+    /// Return the dangerous ingredients, sorted by allergen and comma-separated.
+    /// Needs `inert_ingredients` to be called first.
+    /// Fails when the foods don't tell which ingredient contains each allergen.
+    pub fn dangerous_list(&self) -> Result<String, AssignError<String>> {
+        // Give each allergen a distinct ingredient among those possibly containing it
+        let candidates: BTreeMap<String, BTreeSet<String>> = self
+            .allergens_table
+            .iter()
+            .map(|(allergen, ingredients)| (allergen.clone(), ingredients.iter().cloned().collect()))
+            .collect();
+        // Sorted by allergen name
+        let dangerous: Vec<String> = assign(candidates)?.into_values().collect();
+        Ok(dangerous.join(","))
+    }
+
+    /// Load the foods from a file, like "mxmxvkd kfcds (contains dairy, fish)", one per line
+    pub fn from_file(f_name: &str) -> Result<Self, ParseError> {
+        let str_in = read_file(f_name)?;
+        FoodList::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the foods, like "mxmxvkd kfcds (contains dairy, fish)", one per line
+    pub fn from_string(str_in: &str) -> Result<Self, ParseError> {
+        let mut food = Vec::new();
+        for f in records(str_in) {
+            food.push(FoodList::parse_food(&f)?);
+        }
+        Ok(FoodList {
+            food,
+            allergens_table: HashMap::new(),
+        })
+    }
+
+    fn parse_food(f: &Line) -> Result<Food, ParseError> {
+        // Split the line in 2, at "(contains"
+        let ingr_allergens: Vec<&str> = f.text.split("(contains ").collect();
+        if ingr_allergens.len() != 2 {
+            return Err(f.error(f.text, "expecting `<ingredients> (contains <allergens>)`"));
+        }
+        // Gather ingredients list
+        let ingredients = ingr_allergens[0]
+            .split_whitespace()
+            .map(|s| s.to_string())
+            .collect();
+        let allergens = match ingr_allergens[1].strip_suffix(')') {
+            Some(allergens) => allergens.split(", ").map(|s| s.to_string()).collect(),
+            None => return Err(f.error(ingr_allergens[1], "missing `)` after the allergens")),
+        };
+        Ok(Food {
+            ingredients,
+            allergens,
+        })
+    }
+}
+
+/// Solution of day 21
+pub struct Day21;
+
+impl Solution for Day21 {
+    type Input = FoodList;
+    type Answer1 = usize;
+    type Answer2 = Solved<String>;
+
+    fn parse(input: &str) -> Result<FoodList, ParseError> {
+        FoodList::from_string(input)
+    }
+
+    fn check(input: &str) -> Vec<ParseError> {
+        check_records::<Day21, _, _>(input, FoodList::parse_food)
+    }
+
+    fn part1(food: &FoodList) -> usize {
+        let mut food = food.clone();
+        let inerts = food.inert_ingredients();
+        food.count_inert(&inerts)
+    }
+
+    // The dangerous ingredients, or why the allergens can't be told apart
+    fn part2(food: &FoodList) -> Solved<String> {
+        let mut food = food.clone();
+        food.inert_ingredients();
+        match food.dangerous_list() {
+            Ok(dangerous) => Solved::Answer(dangerous),
+            Err(e) => Solved::none(format!("can't work out the dangerous ingredients: {}", e)),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn ambiguous_allergens() {
+        // either ingredient may contain either allergen, but `c` contains none
+        let food = Day21::parse("a b c (contains dairy, fish)\nb a (contains dairy, fish)\n").unwrap();
+        assert_eq!(1, Day21::part1(&food));
+        assert_eq!(Solved::none("can't work out the dangerous ingredients: several values are possible for `dairy`, `fish`"), Day21::part2(&food));
+    }
+}
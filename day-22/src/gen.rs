@@ -0,0 +1,24 @@
+//! Random decks
+use crate::{Combat, Day22};
+use aoc_common::{Generator, Rng};
+
+impl Generator for Day22 {
+    // cards per player at most, as in the puzzle: with 100 of them, Recursive Combat takes minutes already
+    const MAX_SIZE: usize = 25;
+
+    fn generate(size: usize, rng: &mut Rng) -> String {
+        // the cards 1 to 2 * `size`, shuffled and dealt to both players,
+        // until a game of Combat with them ends
+        let cnt = size.clamp(1, Self::MAX_SIZE);
+        let mut cards: Vec<usize> = (1..=2 * cnt).collect();
+        loop {
+            rng.shuffle(&mut cards);
+            let mut combat = Combat { player_1: cards[..cnt].iter().cloned().collect(), player_2: cards[cnt..].iter().cloned().collect() };
+            if combat.run().is_some() {
+                break;
+            }
+        }
+        let deck = |cards: &[usize]| cards.iter().map(|c| format!("{}\n", c)).collect::<String>();
+        format!("Player 1:\n{}\nPlayer 2:\n{}", deck(&cards[..cnt]), deck(&cards[cnt..]))
+    }
+}
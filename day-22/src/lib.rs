@@ -0,0 +1,268 @@
+//! Day 22: Crab Combat
+//!
+//! Play Combat, then Recursive Combat, against the crab.
+#![warn(missing_docs)]
+
+use aoc_common::{groups, read_file, Frame, Line, NoTrace, ParseError, Solution, Solved, Traced, Tracer};
+use std::collections::{HashSet, VecDeque};
+
+mod gen;
+
+/// The decks of the two players, top card first
+#[derive(Clone)]
+pub struct Combat {
+    /// My deck
+    pub player_1: VecDeque<usize>,
+    /// The crab's deck
+    pub player_2: VecDeque<usize>,
+}
+
+impl Combat {
+    /// Play Combat until a player has all the cards, and return the winner (1 or 2).
+    /// `None` if the decks repeat: without the prevention rule of Recursive Combat, nobody ever wins
+    pub fn run(&mut self) -> Option<u8> {
+        self.run_traced(&mut NoTrace)
+    }
+
+    /// Play Combat as in `run`, recording the decks after each round
+    pub fn run_traced<T: Tracer>(&mut self, tracer: &mut T) -> Option<u8> {
+        let mut winner = 1;
+        let mut round = 0;
+        // the decks at the start of each round
+        let mut seen: HashSet<(VecDeque<usize>, VecDeque<usize>)> = HashSet::new();
+        loop {
+            if self.player_2.is_empty() || self.player_1.is_empty() {
+                // GAME OVER!
+                break;
+            }
+            if !seen.insert((self.player_1.clone(), self.player_2.clone())) {
+                tracer.record(|| Frame::new(format!("level 1 round {}: the decks repeat, the game never ends", round + 1)));
+                return None;
+            }
+            let p2_card = self.player_2.pop_front().unwrap();
+            let p1_card = self.player_1.pop_front().unwrap();
+            if p2_card > p1_card {
+                self.player_2.push_back(p2_card);
+                self.player_2.push_back(p1_card);
+                winner = 2;
+            } else {
+                self.player_1.push_back(p1_card);
+                self.player_1.push_back(p2_card);
+                winner = 1;
+            }
+            round += 1;
+            tracer.record(|| Combat::frame(1, round, winner, &self.player_1, &self.player_2));
+        }
+        Some(winner)
+    }
+
+    /// Play Recursive Combat with the two decks: return 1 if Player 1 win and 2 if Player 2 win
+    pub fn run_recurse(p1_deck: &mut VecDeque<usize>, p2_deck: &mut VecDeque<usize>) -> u8 {
+        Combat::run_recurse_traced(p1_deck, p2_deck, 1, &mut NoTrace)
+    }
+
+    /// Play Recursive Combat as in `run_recurse`, recording the decks after each round of each game.
+    /// `level` is 1 for the main game, 2 for its sub games, and so on.
+    pub fn run_recurse_traced<T: Tracer>(p1_deck: &mut VecDeque<usize>, p2_deck: &mut VecDeque<usize>, level: usize, tracer: &mut T) -> u8 {
+        // In a sub game, the highest card is larger than the nb of cards: it never makes another
+        // sub game, and always wins its round. Its owner can't lose the game, and if it's player 1
+        // there is no need to play it: the decks repeat, or player 2 runs out of cards
+        if level > 1 && p1_deck.iter().max() > p2_deck.iter().max() {
+            tracer.record(|| Frame::new(format!("level {}: player 1 has the highest card, and wins the game", level)));
+            return 1;
+        }
+        let mut winner = 1;
+        let mut round = 0;
+        // the decks at the start of each round of this game
+        let mut seen: HashSet<(VecDeque<usize>, VecDeque<usize>)> = HashSet::new();
+        loop {
+            if p2_deck.is_empty() || p1_deck.is_empty() {
+                // GAME OVER!
+                break;
+            }
+            // Prevention rule: decks already seen in this game, player 1 wins the game
+            if !seen.insert((p1_deck.clone(), p2_deck.clone())) {
+                winner = 1;
+                tracer.record(|| Frame::new(format!("level {} round {}: the decks repeat, player 1 wins the game", level, round + 1)));
+                break;
+            }
+
+            // Draw a card from the top of each deck
+            round += 1;
+            let p1_card = p1_deck.pop_front().unwrap();
+            let p2_card = p2_deck.pop_front().unwrap();
+
+            // If we haven't enough cards on our deck, compare their value
+            if p2_deck.len() < p2_card || p1_deck.len() < p1_card {
+                winner = if p2_card > p1_card { 2 } else { 1 };
+            } else {
+                // Enter a new sub game
+                // Make copies of the deck, keeping only N first cards
+                let mut crab_deck_copy: VecDeque<usize> = p2_deck.clone();
+                crab_deck_copy.truncate(p2_card);
+                let mut my_deck_copy: VecDeque<usize> = p1_deck.clone();
+                my_deck_copy.truncate(p1_card);
+
+                winner = Combat::run_recurse_traced(&mut my_deck_copy, &mut crab_deck_copy, level + 1, tracer);
+            }
+            if winner == 1 {
+                p1_deck.push_back(p1_card);
+                p1_deck.push_back(p2_card);
+            } else {
+                p2_deck.push_back(p2_card);
+                p2_deck.push_back(p1_card);
+            }
+            tracer.record(|| Combat::frame(level, round, winner, p1_deck, p2_deck));
+        }
+        winner
+    }
+
+    fn frame(level: usize, round: usize, winner: u8, p1_deck: &VecDeque<usize>, p2_deck: &VecDeque<usize>) -> Frame {
+        Frame::new(format!("level {} round {}: player {} wins the round", level, round, winner))
+            .with("level", level)
+            .with("round", round)
+            .with("player_1", p1_deck)
+            .with("player_2", p2_deck)
+    }
+
+    /// Score of the deck of `winner` (1 or 2), in a u128 that no deck of usize cards can overflow
+    pub fn score(&self, winner: u8) -> u128 {
+        let cards_and_weight = if winner == 1 {
+            self.player_1.iter().rev().zip(1..)
+        } else {
+            self.player_2.iter().rev().zip(1..)
+        };
+        cards_and_weight.map(|(&card, i): (&usize, u128)| card as u128 * i).sum()
+    }
+
+    /// Load the decks from a file: one card per line, the two decks being separated by an empty line
+    pub fn from_file(f_name: &str) -> Result<Combat, ParseError> {
+        let str_in = read_file(f_name)?;
+        Combat::from_string(&str_in).map_err(|e| e.in_file(f_name))
+    }
+
+    /// Load the decks: one card per line, the two decks being separated by an empty line
+    pub fn from_string(str_in: &str) -> Result<Combat, ParseError> {
+        // split by empty line, getting each deck, starting with a "Player N:" header
+        let mut decks: Vec<VecDeque<usize>> = Vec::new();
+        let mut dealt = HashSet::new();
+        for group in groups(str_in) {
+            Combat::parse_header(&group[0])?;
+            let mut deck = VecDeque::new();
+            for card in &group[1..] {
+                deck.push_back(Combat::deal(card, &mut dealt)?);
+            }
+            decks.push(deck);
+        }
+        if decks.len() != 2 {
+            return Err(ParseError::new(
+                0,
+                0,
+                format!("expecting the decks of 2 players, found {}", decks.len()),
+            ));
+        }
+        let player_2 = decks.pop().unwrap();
+        let player_1 = decks.pop().unwrap();
+        Ok(Combat { player_1, player_2 })
+    }
+
+    fn parse_header(header: &Line) -> Result<(), ParseError> {
+        if !header.text.starts_with("Player") {
+            return Err(header.error(header.text, "expecting a `Player N:` header before the cards"));
+        }
+        Ok(())
+    }
+
+    // The card of a line, not in the cards `dealt` before
+    fn deal(card: &Line, dealt: &mut HashSet<usize>) -> Result<usize, ParseError> {
+        let value = card.parse::<usize>(card.text)?;
+        // the sub games rely on every card being different
+        if !dealt.insert(value) {
+            return Err(card.error(card.text, format!("card {} is dealt twice", value)));
+        }
+        Ok(value)
+    }
+}
+
+/// Solution of day 22
+pub struct Day22;
+
+impl Solution for Day22 {
+    type Input = Combat;
+    type Answer1 = Solved<u128>;
+    type Answer2 = u128;
+
+    fn parse(input: &str) -> Result<Combat, ParseError> {
+        Combat::from_string(input)
+    }
+
+    // every header and card, then the nb of decks
+    fn check(input: &str) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+        let mut dealt = HashSet::new();
+        for group in groups(input) {
+            errors.extend(Combat::parse_header(&group[0]).err());
+            errors.extend(group[1..].iter().filter_map(|card| Combat::deal(card, &mut dealt).err()));
+        }
+        if errors.is_empty() {
+            return Day22::parse(input).err().into_iter().collect();
+        }
+        errors
+    }
+
+    fn part1(play: &Combat) -> Solved<u128> {
+        let mut play = play.clone();
+        match play.run() {
+            Some(winner) => Solved::Answer(play.score(winner)),
+            None => Solved::none("the game of Combat never ends"),
+        }
+    }
+
+    fn part2(play: &Combat) -> u128 {
+        let mut play = play.clone();
+        let winner = Combat::run_recurse(&mut play.player_1, &mut play.player_2);
+        play.score(winner)
+    }
+}
+
+impl Traced for Day22 {
+    fn trace<T: Tracer>(play: &Combat, part: u8, tracer: &mut T) {
+        let mut play = play.clone();
+        if part == 1 {
+            play.run_traced(tracer);
+        } else {
+            Combat::run_recurse_traced(&mut play.player_1, &mut play.player_2, 1, tracer);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn infinite_game() {
+        // from the puzzle: without the prevention rule, the game would go on forever
+        let combat = Day22::parse("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14\n").unwrap();
+        let (mut p1_deck, mut p2_deck) = (combat.player_1, combat.player_2);
+        assert_eq!(1, Combat::run_recurse(&mut p1_deck, &mut p2_deck));
+    }
+
+    #[test]
+    fn endless_combat() {
+        let combat = Day22::parse("Player 1:\n43\n19\n\nPlayer 2:\n2\n29\n14\n").unwrap();
+        assert_eq!(Solved::none("the game of Combat never ends"), Day22::part1(&combat));
+    }
+
+    #[test]
+    fn cards_dealt_twice() {
+        let error = Day22::parse("Player 1:\n3\n1\n\nPlayer 2:\n2\n3\n").err();
+        assert_eq!(Some(ParseError::new(7, 1, "card 3 is dealt twice")), error);
+    }
+
+    #[test]
+    fn check_every_card() {
+        let errors: Vec<String> = Day22::check("Player 1:\n3\nx\n\nPlayer 2:\n2\n3\n-1\n").iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["3:1: invalid number `x`: invalid digit found in string", "7:1: card 3 is dealt twice", "8:1: invalid number `-1`: invalid digit found in string"], errors);
+    }
+}
@@ -0,0 +1,14 @@
+//! Shared by the fuzz targets, one per day: `cargo fuzz run day_08`
+use aoc_common::Solution;
+
+/// Parse and check `input`: both must agree, and neither may panic.
+/// Once parsed, both parts are solved, and must not panic either
+pub fn parse<S: Solution>(input: &str) {
+    let parsed = S::parse(input);
+    let errors = S::check(input);
+    assert_eq!(parsed.is_ok(), errors.is_empty(), "{:?}", errors);
+    if let Ok(parsed) = parsed {
+        S::part1(&parsed).to_string();
+        S::part2(&parsed).to_string();
+    }
+}
@@ -0,0 +1,299 @@
+//! A 2D grid of cells, as found in many puzzles: parsed from a character map,
+//! stored flattened row after row.
+#![warn(missing_docs)]
+
+use aoc_common::{records, Line, ParseError};
+use std::ops::{Add, Index, IndexMut};
+
+/// A position, or a move, on a grid
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Hash)]
+pub struct Xy {
+    /// Column, growing to the right
+    pub x: i32,
+    /// Row, growing downward
+    pub y: i32,
+}
+
+impl Xy {
+    /// The position at column `x` and row `y`
+    pub fn new(x: i32, y: i32) -> Xy {
+        Xy { x, y }
+    }
+}
+
+impl Add for Xy {
+    type Output = Xy;
+
+    fn add(self, other: Xy) -> Xy {
+        Xy { x: self.x + other.x, y: self.y + other.y }
+    }
+}
+
+/// The 4 moves to the orthogonal neighbours: up, left, right and down
+pub const NEIGHBOURS_4: [Xy; 4] = [Xy { x: 0, y: -1 }, Xy { x: -1, y: 0 }, Xy { x: 1, y: 0 }, Xy { x: 0, y: 1 }];
+
+/// The 8 moves to the neighbours, diagonals included, row after row
+pub const NEIGHBOURS_8: [Xy; 8] = [
+    Xy { x: -1, y: -1 },
+    Xy { x: 0, y: -1 },
+    Xy { x: 1, y: -1 },
+    Xy { x: -1, y: 0 },
+    Xy { x: 1, y: 0 },
+    Xy { x: -1, y: 1 },
+    Xy { x: 0, y: 1 },
+    Xy { x: 1, y: 1 },
+];
+
+/// A rectangular grid of cells
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct Grid<T> {
+    width: usize,
+    height: usize,
+    cells: Vec<T>, // row after row
+}
+
+impl<T> Grid<T> {
+    /// A grid made of `cells`, row after row. Panics if there are not `width * height` cells,
+    /// or if the grid is empty: as `parse`, rendering and wrapping around need a cell at least.
+    pub fn new(width: usize, height: usize, cells: Vec<T>) -> Grid<T> {
+        assert!(width > 0 && height > 0, "a grid can't be empty, found {}x{}", width, height);
+        assert_eq!(width * height, cells.len(), "a {}x{} grid needs {} cells", width, height, width * height);
+        Grid { width, height, cells }
+    }
+
+    /// Parse a character map, one row per line, `cell` converting each character.
+    /// `expected` describes the valid characters, for the error messages.
+    /// Blank lines are ignored.
+    pub fn parse(input: &str, expected: &str, cell: impl Fn(char) -> Option<T>) -> Result<Grid<T>, ParseError> {
+        let mut cells = Vec::new();
+        let mut width = 0;
+        let mut height = 0;
+        for l in records(input) {
+            let row = Grid::parse_row(&l, expected, &cell)?;
+            if height == 0 {
+                width = row.len();
+            } else if row.len() != width {
+                return Err(l.error(l.text.trim(), format!("expecting {} cells, found {}", width, row.len())));
+            }
+            cells.extend(row);
+            height += 1;
+        }
+        if height == 0 {
+            return Err(ParseError::new(0, 0, "the grid is empty"));
+        }
+        Ok(Grid { width, height, cells })
+    }
+
+    /// Check a character map as `parse` does, reporting every malformed row instead of the first one.
+    /// The rows must be as wide as the first one.
+    pub fn check(input: &str, expected: &str, cell: impl Fn(char) -> Option<T>) -> Vec<ParseError> {
+        let mut errors = Vec::new();
+        let mut width = None;
+        for l in records(input) {
+            match Grid::parse_row(&l, expected, &cell) {
+                Err(e) => errors.push(e),
+                Ok(row) => match width {
+                    None => width = Some(row.len()),
+                    Some(width) if row.len() != width => {
+                        errors.push(l.error(l.text.trim(), format!("expecting {} cells, found {}", width, row.len())))
+                    }
+                    Some(_) => {}
+                },
+            }
+        }
+        if width.is_none() && errors.is_empty() {
+            errors.push(ParseError::new(0, 0, "the grid is empty"));
+        }
+        errors
+    }
+
+    fn parse_row(l: &Line, expected: &str, cell: impl Fn(char) -> Option<T>) -> Result<Vec<T>, ParseError> {
+        let row = l.text.trim();
+        row.char_indices()
+            .map(|(i, c)| cell(c).ok_or_else(|| l.error(&row[i..], format!("unexpected `{}`, expecting {}", c, expected))))
+            .collect()
+    }
+
+    /// Nb of columns
+    pub fn width(&self) -> usize {
+        self.width
+    }
+
+    /// Nb of rows
+    pub fn height(&self) -> usize {
+        self.height
+    }
+
+    /// Whether `pos` is inside the grid
+    pub fn contains(&self, pos: Xy) -> bool {
+        pos.x >= 0 && pos.y >= 0 && (pos.x as usize) < self.width && (pos.y as usize) < self.height
+    }
+
+    fn idx(&self, pos: Xy) -> usize {
+        pos.y as usize * self.width + pos.x as usize
+    }
+
+    /// The cell at `pos`, or `None` when out of the grid
+    pub fn get(&self, pos: Xy) -> Option<&T> {
+        if self.contains(pos) {
+            Some(&self.cells[self.idx(pos)])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `pos`, or `None` when out of the grid
+    pub fn get_mut(&mut self, pos: Xy) -> Option<&mut T> {
+        if self.contains(pos) {
+            let idx = self.idx(pos);
+            Some(&mut self.cells[idx])
+        } else {
+            None
+        }
+    }
+
+    /// The cell at `pos`, the grid repeating itself infinitely in every direction
+    pub fn get_wrapping(&self, pos: Xy) -> &T {
+        let wrapped = Xy {
+            x: pos.x.rem_euclid(self.width as i32),
+            y: pos.y.rem_euclid(self.height as i32),
+        };
+        &self.cells[self.idx(wrapped)]
+    }
+
+    /// All the positions of the grid, row after row
+    pub fn positions(&self) -> impl Iterator<Item = Xy> {
+        let width = self.width as i32;
+        (0..self.height as i32).flat_map(move |y| (0..width).map(move |x| Xy { x, y }))
+    }
+
+    /// All the cells, row after row
+    pub fn cells(&self) -> impl Iterator<Item = &T> {
+        self.cells.iter()
+    }
+
+    /// The positions of the (up to 8) neighbours of `pos` that are inside the grid
+    pub fn neighbours_8(&self, pos: Xy) -> impl Iterator<Item = Xy> + '_ {
+        NEIGHBOURS_8.iter().map(move |&step| pos + step).filter(move |&n| self.contains(n))
+    }
+
+    /// The positions of the (up to 4) orthogonal neighbours of `pos` that are inside the grid
+    pub fn neighbours_4(&self, pos: Xy) -> impl Iterator<Item = Xy> + '_ {
+        NEIGHBOURS_4.iter().map(move |&step| pos + step).filter(move |&n| self.contains(n))
+    }
+
+    /// Cast a ray from `from` (excluded), moving by `step` until leaving the grid:
+    /// iterate over the positions and the cells met. A `(0, 0)` step meets nothing.
+    pub fn cast(&self, from: Xy, step: Xy) -> impl Iterator<Item = (Xy, &T)> + '_ {
+        let mut pos = from;
+        std::iter::from_fn(move || {
+            if step == Xy::new(0, 0) {
+                return None; // it would never leave the grid
+            }
+            pos = pos + step;
+            self.get(pos).map(|cell| (pos, cell))
+        })
+    }
+
+    /// A grid of the same size, `f` converting each cell
+    pub fn map<U>(&self, f: impl Fn(&T) -> U) -> Grid<U> {
+        Grid { width: self.width, height: self.height, cells: self.cells.iter().map(f).collect() }
+    }
+
+    /// Draw the grid, `f` giving the character of each cell, one line per row
+    pub fn render(&self, f: impl Fn(&T) -> char) -> String {
+        let mut text = String::with_capacity((self.width + 1) * self.height);
+        for row in self.cells.chunks(self.width) {
+            text.extend(row.iter().map(&f));
+            text.push('\n');
+        }
+        text
+    }
+}
+
+impl<T> Index<Xy> for Grid<T> {
+    type Output = T;
+
+    /// Panics when `pos` is out of the grid
+    fn index(&self, pos: Xy) -> &T {
+        self.get(pos).unwrap_or_else(|| panic!("{:?} is out of the {}x{} grid", pos, self.width, self.height))
+    }
+}
+
+impl<T> IndexMut<Xy> for Grid<T> {
+    /// Panics when `pos` is out of the grid
+    fn index_mut(&mut self, pos: Xy) -> &mut T {
+        let (width, height) = (self.width, self.height);
+        self.get_mut(pos).unwrap_or_else(|| panic!("{:?} is out of the {}x{} grid", pos, width, height))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn trees() -> Grid<bool> {
+        Grid::parse("..#\n#..\r\n.#.\n", "`.` or `#`", |c| match c {
+            '#' => Some(true),
+            '.' => Some(false),
+            _ => None,
+        })
+        .unwrap()
+    }
+
+    #[test]
+    fn parse_and_render() {
+        let grid = trees();
+        assert_eq!((3, 3), (grid.width(), grid.height()));
+        assert!(grid[Xy::new(2, 0)]);
+        assert_eq!("..#\n#..\n.#.\n", grid.render(|&tree| if tree { '#' } else { '.' }));
+    }
+
+    #[test]
+    fn parse_errors() {
+        let cell = |c| if c == '.' { Some(()) } else { None };
+        assert_eq!("2:2: unexpected `x`, expecting `.`", Grid::parse("...\n.x.", "`.`", cell).unwrap_err().to_string());
+        assert_eq!("2:1: expecting 3 cells, found 2", Grid::parse("...\n..", "`.`", cell).unwrap_err().to_string());
+        assert!(Grid::parse("\n", "`.`", cell).is_err());
+    }
+
+    #[test]
+    #[should_panic(expected = "a grid can't be empty, found 0x3")]
+    fn empty_grid() {
+        Grid::<bool>::new(0, 3, Vec::new());
+    }
+
+    #[test]
+    fn check_every_row() {
+        let cell = |c| if c == '.' { Some(()) } else { None };
+        let errors: Vec<String> = Grid::check("...\n.x.\n..\n\n.x\n", "`.`", cell).iter().map(|e| e.to_string()).collect();
+        assert_eq!(vec!["2:2: unexpected `x`, expecting `.`", "3:1: expecting 3 cells, found 2", "5:2: unexpected `x`, expecting `.`"], errors);
+        assert_eq!(vec![ParseError::new(0, 0, "the grid is empty")], Grid::check("\n", "`.`", cell));
+    }
+
+    #[test]
+    fn accessors() {
+        let grid = trees();
+        assert_eq!(None, grid.get(Xy::new(3, 0)));
+        assert_eq!(None, grid.get(Xy::new(0, -1)));
+        assert!(*grid.get_wrapping(Xy::new(5, 3)));
+        assert!(*grid.get_wrapping(Xy::new(-3, -2)));
+    }
+
+    #[test]
+    fn neighbours() {
+        let grid = trees();
+        assert_eq!(3, grid.neighbours_8(Xy::new(0, 0)).count());
+        assert_eq!(8, grid.neighbours_8(Xy::new(1, 1)).count());
+        assert_eq!(vec![Xy::new(1, 0), Xy::new(0, 1)], grid.neighbours_4(Xy::new(0, 0)).collect::<Vec<_>>());
+    }
+
+    #[test]
+    fn cast() {
+        let grid = trees();
+        let ray: Vec<(Xy, bool)> = grid.cast(Xy::new(0, 0), Xy::new(1, 1)).map(|(pos, &tree)| (pos, tree)).collect();
+        assert_eq!(vec![(Xy::new(1, 1), false), (Xy::new(2, 2), false)], ray);
+        assert_eq!(Some(Xy::new(2, 0)), grid.cast(Xy::new(0, 0), Xy::new(1, 0)).find(|(_, &tree)| tree).map(|(pos, _)| pos));
+        assert_eq!(0, grid.cast(Xy::new(1, 1), Xy::new(0, 0)).count());
+    }
+}
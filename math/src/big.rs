@@ -0,0 +1,91 @@
+//! The same functions, on arbitrary-precision integers: they never overflow
+use crate::CrtError;
+use num_integer::Integer;
+use num_traits::{One, Signed, Zero};
+
+pub use num_bigint::BigInt;
+
+/// Return `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`
+pub fn extended_gcd(a: &BigInt, b: &BigInt) -> (BigInt, BigInt, BigInt) {
+    let (mut old_r, mut r) = (a.clone(), b.clone());
+    let (mut old_x, mut x) = (BigInt::one(), BigInt::zero());
+    let (mut old_y, mut y) = (BigInt::zero(), BigInt::one());
+    while !r.is_zero() {
+        let q = &old_r / &r;
+        let next_r = &old_r - &q * &r;
+        old_r = std::mem::replace(&mut r, next_r);
+        let next_x = &old_x - &q * &x;
+        old_x = std::mem::replace(&mut x, next_x);
+        let next_y = &old_y - &q * &y;
+        old_y = std::mem::replace(&mut y, next_y);
+    }
+    if old_r.is_negative() {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. `None` when `a` and `m` aren't coprime, or `m` isn't positive.
+pub fn mod_inverse(a: &BigInt, m: &BigInt) -> Option<BigInt> {
+    if !m.is_positive() {
+        return None;
+    }
+    if m.is_one() {
+        return Some(BigInt::zero());
+    }
+    let (g, x, _) = extended_gcd(&a.mod_floor(m), m);
+    if g.is_one() {
+        Some(x.mod_floor(m))
+    } else {
+        None
+    }
+}
+
+/// Solve the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` of `congruences`.
+/// The moduli don't need to be coprime, and the residues may be negative.
+///
+/// Return `(x, m)`: the solutions are the numbers congruent to `x` modulo `m`, the lcm of the
+/// moduli, with `x` in `0..m`. Never fails with [`CrtError::Overflow`].
+pub fn crt(congruences: &[(BigInt, BigInt)]) -> Result<(BigInt, BigInt), CrtError> {
+    let (mut x, mut m) = (BigInt::zero(), BigInt::one());
+    for (i, (residue, modulus)) in congruences.iter().enumerate() {
+        if !modulus.is_positive() {
+            return Err(CrtError::BadModulus(i));
+        }
+        let g = m.gcd(modulus);
+        let (quotient, remainder) = (residue - &x).div_rem(&g);
+        if !remainder.is_zero() {
+            return Err(CrtError::Incompatible(i));
+        }
+        let step = modulus / &g;
+        let inverse = mod_inverse(&(&m / &g), &step).expect("coprime once divided by their gcd");
+        let t = (quotient * inverse).mod_floor(&step);
+        x += &m * t;
+        m *= step;
+    }
+    Ok((x, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn big(congruences: &[(i64, i64)]) -> Vec<(BigInt, BigInt)> {
+        congruences.iter().map(|&(r, m)| (BigInt::from(r), BigInt::from(m))).collect()
+    }
+
+    #[test]
+    fn agrees_with_checked() {
+        assert_eq!(Some(BigInt::from(4)), mod_inverse(&BigInt::from(3), &BigInt::from(11)));
+        assert_eq!(Ok((BigInt::from(10), BigInt::from(12))), crt(&big(&[(2, 4), (-2, 6)])));
+        assert_eq!(Err(CrtError::Incompatible(1)), crt(&big(&[(1, 4), (2, 6)])));
+    }
+
+    #[test]
+    fn past_64_bits() {
+        let (x, m) = crt(&big(&[(1, i64::MAX), (0, 2)])).unwrap();
+        assert_eq!(BigInt::from(i64::MAX) * 2, m);
+        assert_eq!(BigInt::from(i64::MAX) + 1, x);
+    }
+}
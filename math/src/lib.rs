@@ -0,0 +1,153 @@
+//! Number theory for the modular arithmetic puzzles: gcd and lcm, the extended Euclidean
+//! algorithm, modular inverses and the Chinese Remainder Theorem.
+//!
+//! The functions on machine integers are checked: they report an overflow instead of wrapping.
+//! The [`big`] module has the same functions on arbitrary-precision integers.
+#![warn(missing_docs)]
+
+use std::error::Error;
+use std::fmt;
+
+pub mod big;
+
+/// Why a system of congruences can't be solved
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum CrtError {
+    /// The modulus of the congruence at this index isn't positive
+    BadModulus(usize),
+    /// The congruence at this index contradicts the ones before it
+    Incompatible(usize),
+    /// The solution doesn't fit in a `u64`
+    Overflow,
+}
+
+impl fmt::Display for CrtError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            CrtError::BadModulus(i) => write!(f, "the modulus of congruence #{} isn't positive", i),
+            CrtError::Incompatible(i) => write!(f, "congruence #{} contradicts the ones before it", i),
+            CrtError::Overflow => write!(f, "the solution doesn't fit in 64 bits"),
+        }
+    }
+}
+
+impl Error for CrtError {}
+
+/// Greatest common divisor. `gcd(0, 0)` is 0.
+pub fn gcd(a: u64, b: u64) -> u64 {
+    let (mut a, mut b) = (a, b);
+    while b != 0 {
+        let r = a % b;
+        a = b;
+        b = r;
+    }
+    a
+}
+
+/// Least common multiple, or `None` on overflow. `lcm(0, n)` is 0.
+pub fn lcm(a: u64, b: u64) -> Option<u64> {
+    if a == 0 || b == 0 {
+        return Some(0);
+    }
+    (a / gcd(a, b)).checked_mul(b)
+}
+
+/// Return `(g, x, y)` such that `g = gcd(a, b) = a * x + b * y`
+pub fn extended_gcd(a: i128, b: i128) -> (i128, i128, i128) {
+    // invariants: old_r = a * old_x + b * old_y, and r = a * x + b * y
+    let (mut old_r, mut r) = (a, b);
+    let (mut old_x, mut x) = (1, 0);
+    let (mut old_y, mut y) = (0, 1);
+    while r != 0 {
+        let q = old_r / r;
+        (old_r, r) = (r, old_r - q * r);
+        (old_x, x) = (x, old_x - q * x);
+        (old_y, y) = (y, old_y - q * y);
+    }
+    if old_r < 0 {
+        (-old_r, -old_x, -old_y)
+    } else {
+        (old_r, old_x, old_y)
+    }
+}
+
+/// The inverse of `a` modulo `m`, in `0..m`. `None` when `a` and `m` aren't coprime, or `m` is 0.
+pub fn mod_inverse(a: u64, m: u64) -> Option<u64> {
+    if m == 0 {
+        return None;
+    }
+    let (g, x, _) = extended_gcd(i128::from(a % m), i128::from(m));
+    if g != 1 {
+        // 1 is its own inverse modulo 1, as everything else: 0 is the canonical one
+        return if m == 1 { Some(0) } else { None };
+    }
+    Some(x.rem_euclid(i128::from(m)) as u64)
+}
+
+/// Solve the system `x ≡ residue (mod modulus)` for every `(residue, modulus)` of `congruences`.
+/// The moduli don't need to be coprime.
+///
+/// Return `(x, m)`: the solutions are the numbers congruent to `x` modulo `m`, the lcm of the
+/// moduli, with `x` in `0..m`. An empty system gives `(0, 1)`.
+pub fn crt(congruences: &[(u64, u64)]) -> Result<(u64, u64), CrtError> {
+    let (mut x, mut m) = (0u64, 1u64);
+    for (i, &(residue, modulus)) in congruences.iter().enumerate() {
+        if modulus == 0 {
+            return Err(CrtError::BadModulus(i));
+        }
+        let residue = residue % modulus;
+        // x + m * t ≡ residue (mod modulus) has a solution t iff gcd(m, modulus) divides the difference
+        let g = gcd(m, modulus);
+        let diff = i128::from(residue) - i128::from(x);
+        if diff % i128::from(g) != 0 {
+            return Err(CrtError::Incompatible(i));
+        }
+        let step = modulus / g;
+        let lcm = (m / g).checked_mul(modulus).ok_or(CrtError::Overflow)?;
+        // m / g and step are coprime: divide the equation by g and solve for t modulo step
+        let inverse = mod_inverse(m / g, step).expect("coprime once divided by their gcd");
+        let t = (diff / i128::from(g)).rem_euclid(i128::from(step)) as u128 * u128::from(inverse) % u128::from(step);
+        // x < m and t < step: x + m * t < m * step = lcm
+        x = (u128::from(x) + u128::from(m) * t) as u64;
+        m = lcm;
+    }
+    Ok((x, m))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn gcd_lcm() {
+        assert_eq!(6, gcd(12, 18));
+        assert_eq!(7, gcd(0, 7));
+        assert_eq!(Some(36), lcm(12, 18));
+        assert_eq!(None, lcm(u64::MAX, u64::MAX - 1));
+    }
+
+    #[test]
+    fn inverses() {
+        let (g, x, y) = extended_gcd(240, 46);
+        assert_eq!((2, 240 * x + 46 * y), (g, 2));
+        assert_eq!(Some(4), mod_inverse(3, 11));
+        assert_eq!(None, mod_inverse(4, 6));
+        assert_eq!(Some(u64::MAX - 1), mod_inverse(u64::MAX - 1, u64::MAX));
+    }
+
+    #[test]
+    fn coprime_moduli() {
+        // the buses 7,13,x,x,59,x,31,19
+        let buses = [(7, 0), (13, 1), (59, 4), (31, 6), (19, 7)];
+        let congruences: Vec<(u64, u64)> = buses.iter().map(|&(id, offset)| ((id - offset % id) % id, id)).collect();
+        assert_eq!(Ok((1068781, 7 * 13 * 59 * 31 * 19)), crt(&congruences));
+    }
+
+    #[test]
+    fn non_coprime_moduli() {
+        assert_eq!(Ok((10, 12)), crt(&[(2, 4), (4, 6)]));
+        assert_eq!(Err(CrtError::Incompatible(1)), crt(&[(1, 4), (2, 6)]));
+        assert_eq!(Err(CrtError::BadModulus(0)), crt(&[(1, 0)]));
+        assert_eq!(Err(CrtError::Overflow), crt(&[(1, u64::MAX), (0, 2)]));
+    }
+}